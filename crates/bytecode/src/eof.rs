@@ -6,7 +6,7 @@ mod types_section;
 pub mod verification;
 
 pub use body::EofBody;
-pub use header::EofHeader;
+pub use header::{validate_eof_header, EofHeader};
 pub use types_section::TypesSection;
 pub use verification::*;
 
@@ -24,6 +24,14 @@ pub const EOF_MAGIC: u16 = 0xEF00;
 /// EOF magic number in array form
 pub static EOF_MAGIC_BYTES: Bytes = bytes!("ef00");
 
+/// Returns `true` if `bytes` start with the EOF magic `0xEF00` followed by version `0x01`.
+///
+/// Cheap check that does not parse the container.
+#[inline]
+pub const fn has_eof_magic(bytes: &[u8]) -> bool {
+    matches!(bytes, [0xEF, 0x00, 0x01, ..])
+}
+
 /// EVM Object Format (EOF) container
 ///
 /// It consists of a header, body and the raw original bytes.
@@ -207,6 +215,19 @@ mod test {
     use super::*;
     use primitives::bytes;
 
+    #[test]
+    fn eof_magic() {
+        assert!(has_eof_magic(&bytes!(
+            "ef000101000402000100010400000000800000fe"
+        )));
+        assert!(has_eof_magic(&[0xEF, 0x00, 0x01]));
+        assert!(!has_eof_magic(&[0xEF]));
+        assert!(!has_eof_magic(&[0xEF, 0x00]));
+        assert!(!has_eof_magic(&[0xEF, 0x00, 0x02]));
+        assert!(!has_eof_magic(&[0x60, 0x01, 0x60, 0x00]));
+        assert!(!has_eof_magic(&[]));
+    }
+
     #[test]
     fn decode_eof() {
         let bytes = bytes!("ef000101000402000100010400000000800000fe");
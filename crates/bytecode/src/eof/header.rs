@@ -237,6 +237,27 @@ impl EofHeader {
     }
 }
 
+/// Decodes and validates EOF header without decoding the body.
+///
+/// Besides the checks done in [`EofHeader::decode`] (magic, version, section kinds and their
+/// ordering, non-zero code and container sizes, code and types count), it checks that the
+/// input contains the types, code and container sections declared in the header and that
+/// there are no bytes past the declared data section.
+pub fn validate_eof_header(input: &[u8]) -> Result<EofHeader, EofDecodeError> {
+    let (header, body) = EofHeader::decode(input)?;
+
+    let partial_body_len = header.body_size() - header.data_size as usize;
+    if body.len() < partial_body_len {
+        return Err(EofDecodeError::MissingBodyWithoutData);
+    }
+
+    if body.len() > header.body_size() {
+        return Err(EofDecodeError::DanglingData);
+    }
+
+    Ok(header)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,6 +295,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_header() {
+        let input = hex!("ef000101000402000100010400000000800000fe");
+        let header = validate_eof_header(&input).unwrap();
+        assert_eq!(header.types_size, 4);
+        assert_eq!(header.code_sizes, vec![1]);
+        assert_eq!(header.data_size, 0);
+
+        // Body is shorter than declared.
+        let input = hex!("ef000101000402000100010400000000800000");
+        assert_eq!(
+            validate_eof_header(&input),
+            Err(EofDecodeError::MissingBodyWithoutData)
+        );
+    }
+
+    #[test]
+    fn validate_header_missing_data_kind() {
+        // Terminator is placed where data kind is expected.
+        let input = hex!("ef0001010004020001000100000000800000fe");
+        assert_eq!(
+            validate_eof_header(&input),
+            Err(EofDecodeError::InvalidKindAfterCode)
+        );
+    }
+
+    #[test]
+    fn validate_header_count_mismatch() {
+        // Types section declares two code sections but only one code size is present.
+        let input = hex!("ef0001010008020001000104000000");
+        assert_eq!(
+            validate_eof_header(&input),
+            Err(EofDecodeError::MismatchCodeAndTypesSize)
+        );
+    }
+
     #[test]
     fn short_input() {
         let input = hex!("ef0001010000028000");
@@ -57,12 +57,7 @@ pub fn validate_eof_inner(eof: &Eof, first_code_type: Option<CodeType>) -> Resul
         // Validate the current container.
         let tracker_containers = validate_eof_codes(&eof, code_type)?;
         // Decode subcontainers and push them to the stack.
-        for (container, code_type) in eof
-            .body
-            .container_section
-            .iter()
-            .zip(tracker_containers.into_iter())
-        {
+        for (container, code_type) in eof.body.container_section.iter().zip(tracker_containers) {
             stack.push((Cow::Owned(Eof::decode(container.clone())?), Some(code_type)));
         }
     }
@@ -396,6 +391,95 @@ impl fmt::Display for EofValidationError {
 
 impl core::error::Error for EofValidationError {}
 
+/// Checks that `code` contains only opcodes that are known and allowed in EOF.
+///
+/// Immediates, including the `RJUMPV` jump table, are skipped. This is only a subset of
+/// [`validate_eof_code`], stack and jump validity are not checked.
+pub fn validate_eof_opcodes(code: &[u8]) -> Result<(), EofValidationError> {
+    let mut i = 0;
+    while i < code.len() {
+        let op = code[i];
+        let Some(opcode) = OPCODE_INFO[op as usize] else {
+            return Err(EofValidationError::UnknownOpcode);
+        };
+        if opcode.is_disabled_in_eof() {
+            return Err(EofValidationError::OpcodeDisabled);
+        }
+
+        let mut immediate_size = opcode.immediate_size() as usize;
+        if immediate_size != 0 && i + immediate_size >= code.len() {
+            return Err(EofValidationError::MissingImmediateBytes);
+        }
+        if op == opcode::RJUMPV {
+            // Jump table has `max_index + 1` relative offsets.
+            immediate_size += (code[i + 1] as usize + 1) * 2;
+            if i + immediate_size >= code.len() {
+                return Err(EofValidationError::MissingRJUMPVImmediateBytes);
+            }
+        }
+        i += 1 + immediate_size;
+    }
+    Ok(())
+}
+
+/// Checks that the code section ends with a terminating instruction.
+///
+/// Instructions after a terminating instruction are dead code unless they are the target
+/// of an earlier forward `RJUMP`, `RJUMPI` or `RJUMPV`, in which case the section has to end
+/// with a terminating instruction after them. Unreachable trailing code is rejected with
+/// [`InstructionNotForwardAccessed`][EofValidationError::InstructionNotForwardAccessed].
+///
+/// This is only a subset of [`validate_eof_code`], stack and jump validity are not checked.
+pub fn validate_section_termination(code: &[u8]) -> Result<(), EofValidationError> {
+    let mut forward_accessed = vec![false; code.len()];
+    let mut is_after_termination = false;
+    let mut i = 0;
+    while i < code.len() {
+        let op = code[i];
+        let Some(opcode) = OPCODE_INFO[op as usize] else {
+            return Err(EofValidationError::UnknownOpcode);
+        };
+        if is_after_termination && !forward_accessed[i] {
+            return Err(EofValidationError::InstructionNotForwardAccessed);
+        }
+        is_after_termination = opcode.is_terminating();
+
+        let mut immediate_size = opcode.immediate_size() as usize;
+        if immediate_size != 0 && i + immediate_size >= code.len() {
+            return Err(EofValidationError::MissingImmediateBytes);
+        }
+        let mut offsets = 0..0;
+        match op {
+            opcode::RJUMP | opcode::RJUMPI => offsets = i + 1..i + 3,
+            opcode::RJUMPV => {
+                // Jump table has `max_index + 1` relative offsets.
+                immediate_size += (code[i + 1] as usize + 1) * 2;
+                if i + immediate_size >= code.len() {
+                    return Err(EofValidationError::MissingRJUMPVImmediateBytes);
+                }
+                offsets = i + 2..i + 1 + immediate_size;
+            }
+            _ => {}
+        }
+        // Offsets are relative to the next instruction.
+        let next = i + 1 + immediate_size;
+        for offset in code[offsets].chunks_exact(2) {
+            let offset = i16::from_be_bytes([offset[0], offset[1]]);
+            if offset > 0 {
+                if let Some(target) = forward_accessed.get_mut(next + offset as usize) {
+                    *target = true;
+                }
+            }
+        }
+        i = next;
+    }
+
+    if !is_after_termination {
+        return Err(EofValidationError::LastInstructionNotTerminating);
+    }
+    Ok(())
+}
+
 /// Validates that:
 /// * All instructions are valid.
 /// * It ends with a terminating instruction or RJUMP.
@@ -641,11 +725,10 @@ pub fn validate_eof_code(
                 tracker.set_subcontainer_type(index, CodeType::ReturnOrStop)?;
             }
             opcode::RETURN | opcode::STOP => {
-                if *tracker
+                let code_type = tracker
                     .this_container_code_type
-                    .get_or_insert(CodeType::ReturnOrStop)
-                    != CodeType::ReturnOrStop
-                {
+                    .get_or_insert(CodeType::ReturnOrStop);
+                if *code_type != CodeType::ReturnOrStop {
                     return Err(EofValidationError::SubContainerCalledInTwoModes);
                 }
             }
@@ -762,6 +845,98 @@ mod test {
     use super::*;
     use primitives::hex;
 
+    #[test]
+    fn eof_opcodes() {
+        use opcode::{ADD, JUMP, PUSH1, PUSH2, RJUMPV, SELFDESTRUCT, STOP};
+
+        // `JUMP` and `SELFDESTRUCT` bytes in immediates are skipped.
+        let code = [
+            PUSH1,
+            JUMP,
+            PUSH2,
+            SELFDESTRUCT,
+            JUMP,
+            ADD,
+            RJUMPV,
+            0x00,
+            JUMP,
+            0x00,
+            STOP,
+        ];
+        assert_eq!(validate_eof_opcodes(&code), Ok(()));
+
+        assert_eq!(
+            validate_eof_opcodes(&[PUSH1, 0x00, JUMP, STOP]),
+            Err(EofValidationError::OpcodeDisabled)
+        );
+        assert_eq!(
+            validate_eof_opcodes(&[PUSH1, 0x00, SELFDESTRUCT]),
+            Err(EofValidationError::OpcodeDisabled)
+        );
+        assert_eq!(
+            validate_eof_opcodes(&[0x0C, STOP]),
+            Err(EofValidationError::UnknownOpcode)
+        );
+        assert_eq!(
+            validate_eof_opcodes(&[PUSH2, 0x00]),
+            Err(EofValidationError::MissingImmediateBytes)
+        );
+    }
+
+    #[test]
+    fn section_termination() {
+        use opcode::{ADD, PUSH1, RETF, RJUMPI, STOP};
+
+        assert_eq!(
+            validate_section_termination(&[PUSH1, 0x01, PUSH1, 0x02, ADD, RETF]),
+            Ok(())
+        );
+        assert_eq!(
+            validate_section_termination(&[PUSH1, 0x01, PUSH1, 0x02, ADD]),
+            Err(EofValidationError::LastInstructionNotTerminating)
+        );
+        assert_eq!(
+            validate_section_termination(&[]),
+            Err(EofValidationError::LastInstructionNotTerminating)
+        );
+
+        // Dead code after `STOP` is not allowed.
+        assert_eq!(
+            validate_section_termination(&[STOP, PUSH1, 0x01, STOP]),
+            Err(EofValidationError::InstructionNotForwardAccessed)
+        );
+        // Unless it is reachable by a forward jump.
+        let code = [PUSH1, 0x00, RJUMPI, 0x00, 0x01, STOP, ADD, RETF];
+        assert_eq!(validate_section_termination(&code), Ok(()));
+        // Which still has to end with a terminating instruction.
+        assert_eq!(
+            validate_section_termination(&code[..7]),
+            Err(EofValidationError::LastInstructionNotTerminating)
+        );
+    }
+
+    #[test]
+    fn truncated_push() {
+        use opcode::{PUSH2, STOP};
+
+        let types = [TypesSection {
+            inputs: 0,
+            outputs: 0x80,
+            max_stack_size: 1,
+        }];
+        let validate = |code: &[u8]| {
+            let mut tracker = AccessTracker::new(None, 1, 0);
+            validate_eof_code(code, 0, 0, 0, &types, &mut tracker)
+        };
+
+        assert_eq!(validate(&[PUSH2, 0x01, 0x02, STOP]), Ok(()));
+        // `PUSH2` immediate must fit in the section.
+        assert_eq!(
+            validate(&[PUSH2, 0x01]),
+            Err(EofValidationError::MissingImmediateBytes)
+        );
+    }
+
     #[test]
     fn test1() {
         // result:Result { result: false, exception: Some("EOF_ConflictingStackHeight") }
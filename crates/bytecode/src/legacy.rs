@@ -2,6 +2,6 @@ mod analyzed;
 mod jump_map;
 mod raw;
 
-pub use analyzed::LegacyAnalyzedBytecode;
+pub use analyzed::{LegacyAnalyzedBytecode, PcKind};
 pub use jump_map::JumpTable;
-pub use raw::{analyze_legacy, LegacyRawBytecode};
+pub use raw::{analyze_legacy, split_init_and_args, LegacyRawBytecode};
@@ -1,7 +1,27 @@
 use super::JumpTable;
+use crate::opcode::{self, OpCode};
 use bitvec::{bitvec, order::Lsb0};
-use primitives::Bytes;
-use std::sync::Arc;
+use core::ops::Range;
+use primitives::{hex, Address, Bytes};
+use std::{sync::Arc, vec::Vec};
+
+/// Runtime code of an EIP-1167 minimal proxy before the implementation address.
+const MINIMAL_PROXY_PREFIX: [u8; 10] = hex!("363d3d373d3d3d363d73");
+/// Runtime code of an EIP-1167 minimal proxy after the implementation address.
+const MINIMAL_PROXY_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// Kind of the byte at a program counter, see [`LegacyAnalyzedBytecode::pc_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PcKind {
+    /// Start of an instruction.
+    Opcode(OpCode),
+    /// Start of an instruction with an unknown opcode.
+    Unknown(u8),
+    /// Immediate of the `PUSH` instruction at `opcode_pc`.
+    PushData { opcode_pc: usize },
+    /// Position is outside of the original bytecode.
+    OutOfRange,
+}
 
 // Legacy analyzed
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -62,4 +82,331 @@ impl LegacyAnalyzedBytecode {
     pub fn jump_table(&self) -> &JumpTable {
         &self.jump_table
     }
+
+    /// Returns byte ranges of the original bytecode that can't be reached.
+    ///
+    /// This is a heuristic linear scan, not a full reachability analysis: bytes after an
+    /// unconditional terminator (`STOP`, `RETURN`, `REVERT`, `INVALID`, `SELFDESTRUCT`,
+    /// `JUMP` or an unknown opcode) up to the next valid `JUMPDEST` are considered dead.
+    pub fn dead_code_ranges(&self) -> Vec<Range<usize>> {
+        let code = self.original_byte_slice();
+        let mut ranges = Vec::new();
+        let mut dead_start = None;
+        let mut i = 0;
+        while i < code.len() {
+            if self.jump_table.is_valid(i) {
+                if let Some(start) = dead_start.take() {
+                    if start < i {
+                        ranges.push(start..i);
+                    }
+                }
+            }
+
+            let op = code[i];
+            let mut next = i + 1;
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                next += (op - opcode::PUSH0) as usize;
+            }
+
+            if dead_start.is_none()
+                && (op == opcode::JUMP
+                    || OpCode::new(op).is_none_or(|op| op.info().is_terminating()))
+            {
+                dead_start = Some(next.min(code.len()));
+            }
+            i = next;
+        }
+
+        if let Some(start) = dead_start {
+            if start < code.len() {
+                ranges.push(start..code.len());
+            }
+        }
+        ranges
+    }
+
+    /// Returns whether `pc` points to an instruction or inside a `PUSH` immediate.
+    pub fn pc_kind(&self, pc: usize) -> PcKind {
+        let code = self.original_byte_slice();
+        if pc >= code.len() {
+            return PcKind::OutOfRange;
+        }
+
+        let mut i = 0;
+        loop {
+            let op = code[i];
+            if i == pc {
+                return OpCode::new(op).map_or(PcKind::Unknown(op), PcKind::Opcode);
+            }
+            let mut next = i + 1;
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                next += (op - opcode::PUSH0) as usize;
+                if pc < next {
+                    return PcKind::PushData { opcode_pc: i };
+                }
+            }
+            i = next;
+        }
+    }
+
+    /// Returns `true` if `pc` points inside the immediate of a `PUSH` instruction.
+    ///
+    /// Jumps to such positions are invalid even if the byte there is `JUMPDEST`.
+    pub fn is_jump_into_push(&self, pc: usize) -> bool {
+        let code = self.original_byte_slice();
+        let mut i = 0;
+        while i < code.len() && i < pc {
+            let op = code[i];
+            i += 1;
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                i += (op - opcode::PUSH0) as usize;
+                if pc < i {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns positions of `JUMPDEST`s that are not a target of any constant jump.
+    ///
+    /// Only `PUSH` immediately followed by `JUMP` or `JUMPI` is considered a constant jump,
+    /// dynamic jumps are ignored so reported `JUMPDEST`s may still be reachable.
+    pub fn unreachable_jumpdests(&self) -> Vec<usize> {
+        let code = self.original_byte_slice();
+        let mut targets = Vec::new();
+        let mut i = 0;
+        while i < code.len() {
+            let op = code[i];
+            i += 1;
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                let end = i + (op - opcode::PUSH0) as usize;
+                if matches!(code.get(end), Some(&(opcode::JUMP | opcode::JUMPI))) {
+                    let target = &code[i..end];
+                    // Targets that don't fit in `usize` can't be a valid position.
+                    if let Some(target) = target.iter().try_fold(0usize, |acc, &b| {
+                        acc.checked_mul(256)?.checked_add(b as usize)
+                    }) {
+                        targets.push(target);
+                    }
+                }
+                i = end;
+            }
+        }
+
+        (0..code.len())
+            .filter(|&pc| self.jump_table.is_valid(pc) && !targets.contains(&pc))
+            .collect()
+    }
+
+    /// Returns `true` if a `CALL` opcode appears before any `SSTORE` in the bytecode.
+    ///
+    /// This is a reentrancy heuristic based on a linear scan that ignores control flow,
+    /// so it can report both false positives and false negatives.
+    pub fn has_external_call_before_state_write(&self) -> bool {
+        let code = self.original_byte_slice();
+        let mut i = 0;
+        while i < code.len() {
+            match code[i] {
+                opcode::CALL => return true,
+                opcode::SSTORE => return false,
+                op @ opcode::PUSH1..=opcode::PUSH32 => i += (op - opcode::PUSH0) as usize,
+                _ => {}
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Returns the function selectors the bytecode dispatches on, in order of appearance.
+    ///
+    /// This is a heuristic matching the `PUSH4 <selector> EQ PUSH <dest> JUMPI` dispatcher
+    /// emitted by solc, optionally with a `DUP2` before `EQ`. Selectors compared in other
+    /// ways, such as the binary search solc uses for large dispatchers, are not found.
+    pub fn function_selectors(&self) -> Vec<[u8; 4]> {
+        let code = self.original_byte_slice();
+        let mut selectors = Vec::new();
+        let mut i = 0;
+        while i < code.len() {
+            let op = code[i];
+            if op == opcode::PUSH4 {
+                if let Some(selector) = dispatched_selector(&code[i..]) {
+                    if !selectors.contains(&selector) {
+                        selectors.push(selector);
+                    }
+                }
+            }
+            if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                i += (op - opcode::PUSH0) as usize;
+            }
+            i += 1;
+        }
+        selectors
+    }
+
+    /// Returns the implementation address if the bytecode is an EIP-1167 minimal proxy.
+    pub fn as_minimal_proxy(&self) -> Option<Address> {
+        let code = self.original_byte_slice();
+        let rest = code.strip_prefix(&MINIMAL_PROXY_PREFIX)?;
+        let implementation = rest.strip_suffix(&MINIMAL_PROXY_SUFFIX)?;
+        Address::try_from(implementation).ok()
+    }
+}
+
+/// Matches `PUSH4 <selector> [DUP2] EQ PUSH <dest> JUMPI` at the start of `code`.
+fn dispatched_selector(code: &[u8]) -> Option<[u8; 4]> {
+    let selector: [u8; 4] = code.get(1..5)?.try_into().ok()?;
+    let mut rest = &code[5..];
+    if rest.first() == Some(&opcode::DUP2) {
+        rest = &rest[1..];
+    }
+    let (&opcode::EQ, rest) = rest.split_first()? else {
+        return None;
+    };
+    let (&push, rest) = rest.split_first()?;
+    if !(opcode::PUSH1..=opcode::PUSH32).contains(&push) {
+        return None;
+    }
+    let jumpi = rest.get((push - opcode::PUSH0) as usize)?;
+    (*jumpi == opcode::JUMPI).then_some(selector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PcKind;
+    use crate::{
+        opcode::{OpCode, ADD, CALL, EQ, JUMP, JUMPDEST, PUSH1, PUSH2, PUSH4, PUSH5, SSTORE, STOP},
+        LegacyRawBytecode,
+    };
+    use primitives::{address, hex, Bytes};
+    use std::vec;
+
+    #[test]
+    fn dead_code_ranges() {
+        let analyzed =
+            LegacyRawBytecode(Bytes::from(vec![STOP, PUSH1, 0x01, JUMPDEST, STOP])).into_analyzed();
+        assert_eq!(analyzed.dead_code_ranges(), vec![1..3]);
+
+        // `JUMPDEST` inside push data does not end the dead range.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![STOP, PUSH1, JUMPDEST, PUSH1, 0x01]))
+            .into_analyzed();
+        assert_eq!(analyzed.dead_code_ranges(), vec![1..5]);
+
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![PUSH1, 0x01, STOP])).into_analyzed();
+        assert!(analyzed.dead_code_ranges().is_empty());
+    }
+
+    #[test]
+    fn external_call_before_state_write() {
+        // Interaction before effect.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![CALL, PUSH1, 0x01, PUSH1, 0x00, SSTORE]))
+            .into_analyzed();
+        assert!(analyzed.has_external_call_before_state_write());
+
+        // Checks-effects-interactions ordering.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE, CALL]))
+            .into_analyzed();
+        assert!(!analyzed.has_external_call_before_state_write());
+
+        // `CALL` byte inside push data is ignored.
+        let analyzed =
+            LegacyRawBytecode(Bytes::from(vec![PUSH1, CALL, PUSH1, 0x00, SSTORE])).into_analyzed();
+        assert!(!analyzed.has_external_call_before_state_write());
+    }
+
+    #[test]
+    fn function_selectors() {
+        // Dispatcher for `transfer(address,uint256)` and `balanceOf(address)`.
+        let code = hex!(
+            "60003560e01c"
+            "8063a9059cbb14610022578063"
+            "70a0823114610027575b600080fd"
+            "5b005b00"
+        );
+        let analyzed = LegacyRawBytecode(Bytes::copy_from_slice(&code)).into_analyzed();
+        assert_eq!(
+            analyzed.function_selectors(),
+            vec![hex!("a9059cbb"), hex!("70a08231")]
+        );
+
+        // Selector compared without a jump, and `PUSH4` inside push data.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![
+            PUSH4, 0x01, 0x02, 0x03, 0x04, EQ, STOP, PUSH5, PUSH4, 0x01, 0x02, 0x03, 0x04,
+        ]))
+        .into_analyzed();
+        assert!(analyzed.function_selectors().is_empty());
+    }
+
+    #[test]
+    fn clones_share_code() {
+        let analyzed =
+            LegacyRawBytecode(Bytes::from(vec![PUSH1, 0x01, PUSH1, 0x02, ADD])).into_analyzed();
+        let clone = analyzed.clone();
+
+        // Code and jump table are reference counted, clones don't copy them.
+        assert_eq!(clone.bytecode().as_ptr(), analyzed.bytecode().as_ptr());
+        assert_eq!(
+            clone.original_bytes().as_ptr(),
+            analyzed.bytecode().as_ptr()
+        );
+        assert!(std::sync::Arc::ptr_eq(
+            &clone.jump_table().0,
+            &analyzed.jump_table().0
+        ));
+    }
+
+    #[test]
+    fn minimal_proxy() {
+        let code = hex!("363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3");
+        let analyzed = LegacyRawBytecode(Bytes::copy_from_slice(&code)).into_analyzed();
+        assert_eq!(
+            analyzed.as_minimal_proxy(),
+            Some(address!("bebebebebebebebebebebebebebebebebebebebe"))
+        );
+
+        // Implementation address one byte short.
+        let short = [&code[..29], &code[30..]].concat();
+        let analyzed = LegacyRawBytecode(Bytes::from(short)).into_analyzed();
+        assert_eq!(analyzed.as_minimal_proxy(), None);
+
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![PUSH1, 0x01, STOP])).into_analyzed();
+        assert_eq!(analyzed.as_minimal_proxy(), None);
+    }
+
+    #[test]
+    fn jump_into_push() {
+        // `PUSH1 0x5B` followed by a jump to the `JUMPDEST` byte in its immediate.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![PUSH1, JUMPDEST, PUSH1, 0x01, JUMP]))
+            .into_analyzed();
+        assert!(analyzed.is_jump_into_push(1));
+        assert!(!analyzed.jump_table().is_valid(1));
+
+        assert!(!analyzed.is_jump_into_push(0));
+        assert!(!analyzed.is_jump_into_push(2));
+        assert!(analyzed.is_jump_into_push(3));
+        assert!(!analyzed.is_jump_into_push(4));
+    }
+
+    #[test]
+    fn unreachable_jumpdests() {
+        // Jumps to the `JUMPDEST` at 4, the one at 6 is never targeted.
+        let analyzed = LegacyRawBytecode(Bytes::from(vec![
+            PUSH1, 0x04, JUMP, STOP, JUMPDEST, STOP, JUMPDEST, STOP,
+        ]))
+        .into_analyzed();
+        assert_eq!(analyzed.unreachable_jumpdests(), vec![6]);
+    }
+
+    #[test]
+    fn pc_kind() {
+        let analyzed =
+            LegacyRawBytecode(Bytes::from(vec![PUSH2, 0x00, 0x01, ADD, 0x0C])).into_analyzed();
+        assert_eq!(analyzed.pc_kind(0), PcKind::Opcode(OpCode::PUSH2));
+        assert_eq!(analyzed.pc_kind(1), PcKind::PushData { opcode_pc: 0 });
+        assert_eq!(analyzed.pc_kind(2), PcKind::PushData { opcode_pc: 0 });
+        assert_eq!(analyzed.pc_kind(3), PcKind::Opcode(OpCode::ADD));
+        assert_eq!(analyzed.pc_kind(4), PcKind::Unknown(0x0C));
+        // Padding is out of range.
+        assert_eq!(analyzed.pc_kind(5), PcKind::OutOfRange);
+    }
 }
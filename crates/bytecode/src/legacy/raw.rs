@@ -72,3 +72,90 @@ pub fn analyze_legacy(bytetecode: &[u8]) -> JumpTable {
 
     JumpTable(Arc::new(jumps))
 }
+
+/// Splits legacy init code into the code itself and the appended constructor arguments.
+///
+/// This is a heuristic. Code is walked linearly, ignoring jumps, while tracking stack
+/// values that are known from `PUSH` instructions. The static code offset of the last
+/// `CODECOPY` is used as the start of the arguments. If that offset can't be determined
+/// or is out of bounds, the whole input is returned as init code with empty arguments.
+pub fn split_init_and_args(init: &[u8]) -> (&[u8], &[u8]) {
+    let mut stack: Vec<Option<usize>> = Vec::new();
+    let mut args_offset = None;
+    let mut i = 0;
+    while i < init.len() {
+        let op = init[i];
+        i += 1;
+        match op {
+            opcode::PUSH0..=opcode::PUSH32 => {
+                let len = (op - opcode::PUSH0) as usize;
+                let immediate = &init[i..(i + len).min(init.len())];
+                i += len;
+                stack.push(immediate.iter().try_fold(0usize, |acc, &byte| {
+                    acc.checked_mul(256)?.checked_add(byte as usize)
+                }));
+            }
+            opcode::DUP1..=opcode::DUP16 => {
+                let n = (op - opcode::DUP1) as usize + 1;
+                let value = stack.len().checked_sub(n).and_then(|idx| stack[idx]);
+                stack.push(value);
+            }
+            opcode::SWAP1..=opcode::SWAP16 => {
+                let n = (op - opcode::SWAP1) as usize + 1;
+                let len = stack.len();
+                if len > n {
+                    stack.swap(len - 1, len - 1 - n);
+                }
+            }
+            _ => {
+                let (inputs, outputs) = opcode::OpCode::new(op)
+                    .map(|op| op.input_output())
+                    .unwrap_or_default();
+                let len = stack.len();
+                if op == opcode::CODECOPY {
+                    // Stack inputs are `destOffset`, `offset` and `size`.
+                    args_offset = len.checked_sub(2).and_then(|idx| stack[idx]);
+                }
+                stack.truncate(len.saturating_sub(inputs as usize));
+                stack.extend((0..outputs).map(|_| None));
+            }
+        }
+    }
+
+    match args_offset {
+        Some(offset) if offset <= init.len() => init.split_at(offset),
+        _ => (init, &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode::*;
+
+    #[test]
+    fn push0_has_no_immediate() {
+        let jump_table = analyze_legacy(&[PUSH0, JUMPDEST, PUSH1, JUMPDEST]);
+        assert!(jump_table.is_valid(1));
+        assert!(!jump_table.is_valid(3));
+    }
+
+    #[test]
+    fn split_constructor_args() {
+        // Copies the 32 byte argument after the code to memory and stores it.
+        let code = [
+            PUSH1, 0x20, PUSH1, 0x0E, PUSH1, 0x00, CODECOPY, PUSH1, 0x00, MLOAD, PUSH1, 0x00,
+            SSTORE, STOP,
+        ];
+        let arg = [0xAB; 32];
+        let mut init = code.to_vec();
+        init.extend_from_slice(&arg);
+
+        let (init_code, args) = split_init_and_args(&init);
+        assert_eq!(init_code, &code);
+        assert_eq!(args, &arg);
+
+        // No `CODECOPY`, everything is init code.
+        assert_eq!(split_init_and_args(&code[7..]), (&code[7..], &[][..]));
+    }
+}
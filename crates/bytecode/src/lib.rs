@@ -17,10 +17,14 @@ pub use bitvec;
 pub use bytecode::Bytecode;
 pub use decode_errors::BytecodeDecodeError;
 pub use eof::{
+    has_eof_magic,
     verification::{
-        validate_eof, validate_eof_code, validate_eof_codes, validate_eof_inner, validate_raw_eof,
-        validate_raw_eof_inner, CodeType, EofValidationError,
+        validate_eof, validate_eof_code, validate_eof_codes, validate_eof_inner,
+        validate_eof_opcodes, validate_raw_eof, validate_raw_eof_inner,
+        validate_section_termination, CodeType, EofValidationError,
     },
     Eof, EOF_MAGIC, EOF_MAGIC_BYTES, EOF_MAGIC_HASH,
 };
-pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
+pub use legacy::{
+    split_init_and_args, JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode, PcKind,
+};
@@ -4,6 +4,7 @@
 pub mod parse;
 
 use core::{fmt, ptr::NonNull};
+use specification::hardfork::SpecId;
 
 /// An EVM opcode
 ///
@@ -100,6 +101,18 @@ impl OpCode {
         self.info().name()
     }
 
+    /// Returns the opcode mnemonic used in the given spec.
+    ///
+    /// Same as [`as_str`](Self::as_str), except for `0x44` which is rendered as `DIFFICULTY`
+    /// before Paris and as `PREVRANDAO` after it (EIP-4399).
+    #[inline]
+    pub const fn mnemonic(&self, spec: SpecId) -> &'static str {
+        if self.0 == DIFFICULTY && spec.is_enabled_in(SpecId::MERGE) {
+            return "PREVRANDAO";
+        }
+        self.as_str()
+    }
+
     /// Returns the opcode name.
     #[inline]
     pub const fn name_by_op(opcode: u8) -> &'static str {
@@ -223,6 +236,10 @@ pub struct OpCodeInfo {
     not_eof: bool,
     /// If the opcode stops execution. aka STOP, RETURN, ..
     terminating: bool,
+    /// Only valid in EOF bytecode.
+    eof_only: bool,
+    /// Spec the opcode was introduced in.
+    enabled_in: SpecId,
 }
 
 impl fmt::Debug for OpCodeInfo {
@@ -234,6 +251,8 @@ impl fmt::Debug for OpCodeInfo {
             .field("not_eof", &self.is_disabled_in_eof())
             .field("terminating", &self.is_terminating())
             .field("immediate_size", &self.immediate_size())
+            .field("eof_only", &self.is_eof_only())
+            .field("enabled_in", &self.enabled_in())
             .finish()
     }
 }
@@ -250,6 +269,8 @@ impl OpCodeInfo {
             not_eof: false,
             terminating: false,
             immediate_size: 0,
+            eof_only: false,
+            enabled_in: SpecId::FRONTIER,
         }
     }
 
@@ -299,6 +320,24 @@ impl OpCodeInfo {
     pub const fn immediate_size(&self) -> u8 {
         self.immediate_size
     }
+
+    /// Returns whether this opcode is only valid in EOF bytecode.
+    #[inline]
+    pub const fn is_eof_only(&self) -> bool {
+        self.eof_only
+    }
+
+    /// Returns the spec this opcode was introduced in.
+    #[inline]
+    pub const fn enabled_in(&self) -> SpecId {
+        self.enabled_in
+    }
+
+    /// Returns whether this opcode is available in the given spec.
+    #[inline]
+    pub const fn is_enabled_in(&self, spec: SpecId) -> bool {
+        spec.is_enabled_in(self.enabled_in)
+    }
 }
 
 /// Sets the EOF flag to false.
@@ -325,6 +364,21 @@ pub const fn terminating(mut op: OpCodeInfo) -> OpCodeInfo {
     op
 }
 
+/// Marks the opcode as only valid in EOF bytecode, which is enabled in Osaka.
+#[inline]
+pub const fn eof_only(mut op: OpCodeInfo) -> OpCodeInfo {
+    op.eof_only = true;
+    op.enabled_in = SpecId::OSAKA;
+    op
+}
+
+/// Sets the spec the opcode was introduced in.
+#[inline]
+pub const fn enabled_in(mut op: OpCodeInfo, spec: SpecId) -> OpCodeInfo {
+    op.enabled_in = spec;
+    op
+}
+
 /// Sets the number of stack inputs and outputs.
 #[inline]
 pub const fn stack_io(mut op: OpCodeInfo, inputs: u8, outputs: u8) -> OpCodeInfo {
@@ -336,6 +390,14 @@ pub const fn stack_io(mut op: OpCodeInfo, inputs: u8, outputs: u8) -> OpCodeInfo
 /// Alias for the [`JUMPDEST`] opcode
 pub const NOP: u8 = JUMPDEST;
 
+/// Alias for the [`DIFFICULTY`] opcode, renamed in EIP-4399
+pub const PREVRANDAO: u8 = DIFFICULTY;
+
+impl OpCode {
+    /// Alias for the [`DIFFICULTY`](Self::DIFFICULTY) opcode, renamed in EIP-4399.
+    pub const PREVRANDAO: Self = Self::DIFFICULTY;
+}
+
 macro_rules! opcodes {
     ($($val:literal => $name:ident => $($modifier:ident $(( $($modifier_arg:expr),* ))?),*);* $(;)?) => {
         // Constants for each opcode. This also takes care of duplicate names.
@@ -425,9 +487,9 @@ opcodes! {
     0x18 => XOR  => stack_io(2, 1);
     0x19 => NOT  => stack_io(1, 1);
     0x1A => BYTE => stack_io(2, 1);
-    0x1B => SHL  => stack_io(2, 1);
-    0x1C => SHR  => stack_io(2, 1);
-    0x1D => SAR  => stack_io(2, 1);
+    0x1B => SHL  => stack_io(2, 1), enabled_in(SpecId::CONSTANTINOPLE);
+    0x1C => SHR  => stack_io(2, 1), enabled_in(SpecId::CONSTANTINOPLE);
+    0x1D => SAR  => stack_io(2, 1), enabled_in(SpecId::CONSTANTINOPLE);
     // 0x1E
     // 0x1F
     0x20 => KECCAK256 => stack_io(2, 1);
@@ -460,20 +522,20 @@ opcodes! {
     0x3A => GASPRICE     => stack_io(0, 1);
     0x3B => EXTCODESIZE  => stack_io(1, 1), not_eof;
     0x3C => EXTCODECOPY  => stack_io(4, 0), not_eof;
-    0x3D => RETURNDATASIZE => stack_io(0, 1);
-    0x3E => RETURNDATACOPY => stack_io(3, 0);
-    0x3F => EXTCODEHASH  => stack_io(1, 1), not_eof;
+    0x3D => RETURNDATASIZE => stack_io(0, 1), enabled_in(SpecId::BYZANTIUM);
+    0x3E => RETURNDATACOPY => stack_io(3, 0), enabled_in(SpecId::BYZANTIUM);
+    0x3F => EXTCODEHASH  => stack_io(1, 1), not_eof, enabled_in(SpecId::CONSTANTINOPLE);
     0x40 => BLOCKHASH    => stack_io(1, 1);
     0x41 => COINBASE     => stack_io(0, 1);
     0x42 => TIMESTAMP    => stack_io(0, 1);
     0x43 => NUMBER       => stack_io(0, 1);
     0x44 => DIFFICULTY   => stack_io(0, 1);
     0x45 => GASLIMIT     => stack_io(0, 1);
-    0x46 => CHAINID      => stack_io(0, 1);
-    0x47 => SELFBALANCE  => stack_io(0, 1);
-    0x48 => BASEFEE      => stack_io(0, 1);
-    0x49 => BLOBHASH     => stack_io(1, 1);
-    0x4A => BLOBBASEFEE  => stack_io(0, 1);
+    0x46 => CHAINID      => stack_io(0, 1), enabled_in(SpecId::ISTANBUL);
+    0x47 => SELFBALANCE  => stack_io(0, 1), enabled_in(SpecId::ISTANBUL);
+    0x48 => BASEFEE      => stack_io(0, 1), enabled_in(SpecId::LONDON);
+    0x49 => BLOBHASH     => stack_io(1, 1), enabled_in(SpecId::CANCUN);
+    0x4A => BLOBBASEFEE  => stack_io(0, 1), enabled_in(SpecId::CANCUN);
     // 0x4B
     // 0x4C
     // 0x4D
@@ -491,11 +553,11 @@ opcodes! {
     0x59 => MSIZE    => stack_io(0, 1);
     0x5A => GAS      => stack_io(0, 1), not_eof;
     0x5B => JUMPDEST => stack_io(0, 0);
-    0x5C => TLOAD    => stack_io(1, 1);
-    0x5D => TSTORE   => stack_io(2, 0);
-    0x5E => MCOPY    => stack_io(3, 0);
+    0x5C => TLOAD    => stack_io(1, 1), enabled_in(SpecId::CANCUN);
+    0x5D => TSTORE   => stack_io(2, 0), enabled_in(SpecId::CANCUN);
+    0x5E => MCOPY    => stack_io(3, 0), enabled_in(SpecId::CANCUN);
 
-    0x5F => PUSH0  => stack_io(0, 1);
+    0x5F => PUSH0  => stack_io(0, 1), enabled_in(SpecId::SHANGHAI);
     0x60 => PUSH1  => stack_io(0, 1), immediate_size(1);
     0x61 => PUSH2  => stack_io(0, 1), immediate_size(2);
     0x62 => PUSH3  => stack_io(0, 1), immediate_size(3);
@@ -611,10 +673,10 @@ opcodes! {
     // 0xCD
     // 0xCE
     // 0xCF
-    0xD0 => DATALOAD=> stack_io(1, 1);
-    0xD1 => DATALOADN => stack_io(0, 1), immediate_size(2);
-    0xD2 => DATASIZE=> stack_io(0, 1);
-    0xD3 => DATACOPY=> stack_io(3, 0);
+    0xD0 => DATALOAD=> stack_io(1, 1), eof_only;
+    0xD1 => DATALOADN => stack_io(0, 1), immediate_size(2), eof_only;
+    0xD2 => DATASIZE=> stack_io(0, 1), eof_only;
+    0xD3 => DATACOPY=> stack_io(3, 0), eof_only;
     // 0xD4
     // 0xD5
     // 0xD6
@@ -627,36 +689,36 @@ opcodes! {
     // 0xDD
     // 0xDE
     // 0xDF
-    0xE0 => RJUMP    => stack_io(0, 0), immediate_size(2), terminating;
-    0xE1 => RJUMPI   => stack_io(1, 0), immediate_size(2);
-    0xE2 => RJUMPV   => stack_io(1, 0), immediate_size(1);
-    0xE3 => CALLF    => stack_io(0, 0), immediate_size(2);
-    0xE4 => RETF     => stack_io(0, 0), terminating;
-    0xE5 => JUMPF    => stack_io(0, 0), immediate_size(2), terminating;
-    0xE6 => DUPN     => stack_io(0, 1), immediate_size(1);
-    0xE7 => SWAPN    => stack_io(0, 0), immediate_size(1);
-    0xE8 => EXCHANGE => stack_io(0, 0), immediate_size(1);
+    0xE0 => RJUMP    => stack_io(0, 0), immediate_size(2), terminating, eof_only;
+    0xE1 => RJUMPI   => stack_io(1, 0), immediate_size(2), eof_only;
+    0xE2 => RJUMPV   => stack_io(1, 0), immediate_size(1), eof_only;
+    0xE3 => CALLF    => stack_io(0, 0), immediate_size(2), eof_only;
+    0xE4 => RETF     => stack_io(0, 0), terminating, eof_only;
+    0xE5 => JUMPF    => stack_io(0, 0), immediate_size(2), terminating, eof_only;
+    0xE6 => DUPN     => stack_io(0, 1), immediate_size(1), eof_only;
+    0xE7 => SWAPN    => stack_io(0, 0), immediate_size(1), eof_only;
+    0xE8 => EXCHANGE => stack_io(0, 0), immediate_size(1), eof_only;
     // 0xE9
     // 0xEA
     // 0xEB
-    0xEC => EOFCREATE      => stack_io(4, 1), immediate_size(1);
+    0xEC => EOFCREATE      => stack_io(4, 1), immediate_size(1), eof_only;
     // 0xED
-    0xEE => RETURNCONTRACT => stack_io(2, 0), immediate_size(1), terminating;
+    0xEE => RETURNCONTRACT => stack_io(2, 0), immediate_size(1), terminating, eof_only;
     // 0xEF
     0xF0 => CREATE       => stack_io(3, 1), not_eof;
     0xF1 => CALL         => stack_io(7, 1), not_eof;
     0xF2 => CALLCODE     => stack_io(7, 1), not_eof;
     0xF3 => RETURN       => stack_io(2, 0), terminating;
-    0xF4 => DELEGATECALL => stack_io(6, 1), not_eof;
-    0xF5 => CREATE2      => stack_io(4, 1), not_eof;
+    0xF4 => DELEGATECALL => stack_io(6, 1), not_eof, enabled_in(SpecId::HOMESTEAD);
+    0xF5 => CREATE2      => stack_io(4, 1), not_eof, enabled_in(SpecId::PETERSBURG);
     // 0xF6
-    0xF7 => RETURNDATALOAD  => stack_io(1, 1);
-    0xF8 => EXTCALL         => stack_io(4, 1);
-    0xF9 => EXTDELEGATECALL => stack_io(3, 1);
-    0xFA => STATICCALL      => stack_io(6, 1), not_eof;
-    0xFB => EXTSTATICCALL   => stack_io(3, 1);
+    0xF7 => RETURNDATALOAD  => stack_io(1, 1), eof_only;
+    0xF8 => EXTCALL         => stack_io(4, 1), eof_only;
+    0xF9 => EXTDELEGATECALL => stack_io(3, 1), eof_only;
+    0xFA => STATICCALL      => stack_io(6, 1), not_eof, enabled_in(SpecId::BYZANTIUM);
+    0xFB => EXTSTATICCALL   => stack_io(3, 1), eof_only;
     // 0xFC
-    0xFD => REVERT       => stack_io(2, 0), terminating;
+    0xFD => REVERT       => stack_io(2, 0), terminating, enabled_in(SpecId::BYZANTIUM);
     0xFE => INVALID      => stack_io(0, 0), terminating;
     0xFF => SELFDESTRUCT => stack_io(1, 0), not_eof, terminating;
 }
@@ -665,6 +727,21 @@ opcodes! {
 mod tests {
     use super::*;
 
+    #[test]
+    fn difficulty_prevrandao_mnemonic() {
+        assert_eq!(OpCode::PREVRANDAO, OpCode::DIFFICULTY);
+        assert_eq!(OpCode::DIFFICULTY.mnemonic(SpecId::LONDON), "DIFFICULTY");
+        assert_eq!(
+            OpCode::DIFFICULTY.mnemonic(SpecId::GRAY_GLACIER),
+            "DIFFICULTY"
+        );
+        assert_eq!(OpCode::DIFFICULTY.mnemonic(SpecId::MERGE), "PREVRANDAO");
+        assert_eq!(OpCode::DIFFICULTY.mnemonic(SpecId::CANCUN), "PREVRANDAO");
+        // Other opcodes are not spec dependent.
+        assert_eq!(OpCode::ADD.mnemonic(SpecId::FRONTIER), "ADD");
+        assert_eq!(OpCode::ADD.mnemonic(SpecId::CANCUN), "ADD");
+    }
+
     #[test]
     fn test_opcode() {
         let opcode = OpCode::new(0x00).unwrap();
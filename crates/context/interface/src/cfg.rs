@@ -1,6 +1,7 @@
 use auto_impl::auto_impl;
 use core::fmt::Debug;
 use core::hash::Hash;
+use core::num::NonZeroU64;
 use primitives::{TxKind, U256};
 use specification::hardfork::SpecId;
 
@@ -26,6 +27,17 @@ pub trait Cfg {
     fn is_nonce_check_disabled(&self) -> bool;
 
     fn is_base_fee_check_disabled(&self) -> bool;
+
+    /// Returns `true` if `PUSH` instructions with an immediate that extends past the end of
+    /// legacy bytecode halt instead of reading the missing bytes as zeros.
+    fn is_strict_push_enabled(&self) -> bool {
+        false
+    }
+
+    /// Denominator of the quadratic part of the memory expansion cost, `512` on mainnet.
+    fn memory_quad_coeff_div(&self) -> NonZeroU64 {
+        NonZeroU64::new(512).unwrap()
+    }
 }
 
 /// What bytecode analysis to perform
@@ -1,10 +1,12 @@
 mod dummy;
+mod empty;
 
 pub use crate::journaled_state::StateLoad;
 pub use dummy::DummyHost;
+pub use empty::EmptyHost;
 
 use crate::{
-    journaled_state::{AccountLoad, Eip7702CodeLoad},
+    journaled_state::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint},
     Block, Cfg, Transaction,
 };
 use primitives::{Address, Bytes, Log, B256, U256};
@@ -29,6 +31,14 @@ pub trait Host {
     /// Loads an account code.
     fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad>;
 
+    /// Returns the blob base fee of the current block.
+    ///
+    /// Zero if the block does not have blob gas pricing.
+    #[inline]
+    fn blob_base_fee(&self) -> U256 {
+        U256::from(self.block().blob_gasprice().unwrap_or_default())
+    }
+
     /// Gets the block hash of the given block `number`.
     fn block_hash(&mut self, number: u64) -> Option<B256>;
 
@@ -69,6 +79,55 @@ pub trait Host {
         address: Address,
         target: Address,
     ) -> Option<StateLoad<SelfDestructResult>>;
+
+    /// Creates a checkpoint of the state changes made through the host.
+    ///
+    /// Changes made after the checkpoint are kept with [`Host::checkpoint_commit`] or
+    /// discarded with [`Host::checkpoint_revert`].
+    fn checkpoint(&mut self) -> JournalCheckpoint;
+
+    /// Keeps the state changes made since the last checkpoint.
+    fn checkpoint_commit(&mut self);
+
+    /// Reverts the state changes made since `checkpoint` was created.
+    fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint);
+
+    /// Called when `ADD`, `SUB` or `MUL` overflows and the result wraps around.
+    ///
+    /// Fuzzers can use this to compare against reference implementations,
+    /// default implementation does nothing.
+    #[inline]
+    fn arithmetic_wrap(&mut self, opcode: u8) {
+        let _ = opcode;
+    }
+
+    /// Called when an instruction changes the gas refund by `delta`.
+    ///
+    /// `new_total` is the refund counter after the change. Tracers can use this
+    /// to reconstruct the refund timeline, default implementation does nothing.
+    #[inline]
+    fn trace_refund(&mut self, delta: i64, new_total: i64) {
+        let _ = (delta, new_total);
+    }
+
+    /// Called after an instruction writes `data` to memory at `offset`.
+    ///
+    /// Fired by `MSTORE`, `MSTORE8`, `MCOPY` and the `*COPY` instructions. Tracers can use
+    /// this to maintain a shadow memory, default implementation does nothing.
+    #[inline]
+    fn trace_memory_write(&mut self, offset: usize, data: &[u8]) {
+        let _ = (offset, data);
+    }
+
+    /// Called when a `CALL`, `CALLCODE`, `DELEGATECALL` or `STATICCALL` sub call returns
+    /// to its caller.
+    ///
+    /// Tracers can use this to explain the gas of a call, default implementation does
+    /// nothing.
+    #[inline]
+    fn trace_call_gas(&mut self, breakdown: &CallGasBreakdown) {
+        let _ = breakdown;
+    }
 }
 
 /// Represents the result of an `sstore` operation.
@@ -121,6 +180,37 @@ impl SStoreResult {
     }
 }
 
+/// Gas components of a single `CALL`, reported by [`Host::trace_call_gas`].
+///
+/// The caller is charged [`cost`][CallGasBreakdown::cost] plus `forwarded` and gets
+/// `returned` back when the sub call ends.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallGasBreakdown {
+    /// Flat call cost. Warm account access cost since Berlin.
+    pub base: u64,
+    /// Cold account access surcharge, including the EIP-7702 delegate account access.
+    pub access: u64,
+    /// Value transfer cost.
+    pub value_transfer: u64,
+    /// New account creation cost.
+    pub new_account: u64,
+    /// Gas stipend given to the callee for free on value transfer.
+    pub stipend: u64,
+    /// Gas forwarded to the callee, without the stipend.
+    pub forwarded: u64,
+    /// Gas returned to the caller when the sub call ends.
+    pub returned: u64,
+}
+
+impl CallGasBreakdown {
+    /// Returns the gas charged for the call itself, excluding forwarded gas.
+    #[inline]
+    pub const fn cost(&self) -> u64 {
+        self.base + self.access + self.value_transfer + self.new_account
+    }
+}
+
 /// Result of a selfdestruct action
 ///
 /// Value returned are needed to calculate the gas spent.
@@ -1,9 +1,11 @@
 use super::{Host, SStoreResult, SelfDestructResult};
 use crate::{Block, Cfg, Transaction};
-use primitives::{hash_map::Entry, Address, Bytes, HashMap, Log, B256, KECCAK_EMPTY, U256};
+use primitives::{
+    hash_map::Entry, Address, Bytes, HashMap, Log, B256, BLOCK_HASH_HISTORY, KECCAK_EMPTY, U256,
+};
 use std::vec::Vec;
 
-use super::{AccountLoad, Eip7702CodeLoad, StateLoad};
+use super::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint, StateLoad};
 
 /// A dummy [Host] implementation.
 #[derive(Clone, Debug, Default)]
@@ -18,7 +20,14 @@ where
     pub cfg: CFG,
     pub storage: HashMap<U256, U256>,
     pub transient_storage: HashMap<U256, U256>,
+    pub block_hashes: HashMap<u64, B256>,
     pub log: Vec<Log>,
+    /// Number of wrapping `ADD`/`SUB`/`MUL` operations, `None` if tracking is disabled.
+    pub arithmetic_wraps: Option<u64>,
+    /// Memory writes as `(offset, data)`, `None` if tracking is disabled.
+    pub memory_writes: Option<Vec<(usize, Bytes)>>,
+    /// Storage and transient storage saved by [`Host::checkpoint`].
+    checkpoints: Vec<(HashMap<U256, U256>, HashMap<U256, U256>)>,
 }
 
 impl<BLOCK, TX, CFG> DummyHost<BLOCK, TX, CFG>
@@ -36,10 +45,41 @@ where
             cfg: CFG::default(),
             storage: HashMap::default(),
             transient_storage: HashMap::default(),
+            block_hashes: HashMap::default(),
             log: Vec::new(),
+            arithmetic_wraps: None,
+            memory_writes: None,
+            checkpoints: Vec::new(),
         }
     }
 
+    /// Sets the block hashes returned by [`Host::block_hash`].
+    ///
+    /// Only the [`BLOCK_HASH_HISTORY`] most recent blocks are kept, numbers that are
+    /// not present resolve to zero.
+    pub fn with_block_hashes(mut self, mut hashes: Vec<(u64, B256)>) -> Self {
+        hashes.sort_unstable_by_key(|(number, _)| core::cmp::Reverse(*number));
+        hashes.truncate(BLOCK_HASH_HISTORY as usize);
+        self.block_hashes = hashes.into_iter().collect();
+        self
+    }
+
+    /// Enables counting of wrapping arithmetic reported by [`Host::arithmetic_wrap`].
+    ///
+    /// The count is available in [`arithmetic_wraps`][Self::arithmetic_wraps].
+    pub fn with_wrap_tracking(mut self) -> Self {
+        self.arithmetic_wraps = Some(0);
+        self
+    }
+
+    /// Enables recording of memory writes reported by [`Host::trace_memory_write`].
+    ///
+    /// The writes are available in [`memory_writes`][Self::memory_writes].
+    pub fn with_memory_write_tracking(mut self) -> Self {
+        self.memory_writes = Some(Vec::new());
+        self
+    }
+
     /// Clears the storage and logs of the dummy host.
     #[inline]
     pub fn clear(&mut self) {
@@ -74,8 +114,8 @@ impl<TX: Transaction, BLOCK: Block, CFG: Cfg> Host for DummyHost<BLOCK, TX, CFG>
     }
 
     #[inline]
-    fn block_hash(&mut self, _number: u64) -> Option<B256> {
-        Some(B256::ZERO)
+    fn block_hash(&mut self, number: u64) -> Option<B256> {
+        Some(self.block_hashes.get(&number).copied().unwrap_or_default())
     }
 
     #[inline]
@@ -140,6 +180,20 @@ impl<TX: Transaction, BLOCK: Block, CFG: Cfg> Host for DummyHost<BLOCK, TX, CFG>
         self.log.push(log)
     }
 
+    #[inline]
+    fn arithmetic_wrap(&mut self, _opcode: u8) {
+        if let Some(wraps) = &mut self.arithmetic_wraps {
+            *wraps += 1;
+        }
+    }
+
+    #[inline]
+    fn trace_memory_write(&mut self, offset: usize, data: &[u8]) {
+        if let Some(writes) = &mut self.memory_writes {
+            writes.push((offset, Bytes::copy_from_slice(data)));
+        }
+    }
+
     #[inline]
     fn selfdestruct(
         &mut self,
@@ -148,4 +202,29 @@ impl<TX: Transaction, BLOCK: Block, CFG: Cfg> Host for DummyHost<BLOCK, TX, CFG>
     ) -> Option<StateLoad<SelfDestructResult>> {
         Some(StateLoad::default())
     }
+
+    #[inline]
+    fn checkpoint(&mut self) -> JournalCheckpoint {
+        self.checkpoints
+            .push((self.storage.clone(), self.transient_storage.clone()));
+        JournalCheckpoint {
+            log_i: self.log.len(),
+            journal_i: self.checkpoints.len() - 1,
+        }
+    }
+
+    #[inline]
+    fn checkpoint_commit(&mut self) {
+        self.checkpoints.pop();
+    }
+
+    #[inline]
+    fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
+        self.checkpoints.truncate(checkpoint.journal_i + 1);
+        if let Some((storage, transient_storage)) = self.checkpoints.pop() {
+            self.storage = storage;
+            self.transient_storage = transient_storage;
+        }
+        self.log.truncate(checkpoint.log_i);
+    }
 }
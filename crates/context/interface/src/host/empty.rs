@@ -0,0 +1,124 @@
+use super::{Host, SStoreResult, SelfDestructResult};
+use crate::{Block, Cfg, Transaction};
+use primitives::{Address, Bytes, Log, B256, U256};
+
+use super::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint, StateLoad};
+
+/// A [Host] without any state.
+///
+/// Only holds the environment, so code that uses only arithmetic, stack and memory
+/// can be run with minimal setup. State accessing methods return `None`, which makes
+/// instructions like `SLOAD` or `CALL` fail with a `FatalExternalError`, the same
+/// result any host gives when state can't be loaded.
+/// Transient storage always reads zero and logs are discarded.
+///
+/// The host is not zero-sized: [Host] hands out the environment by reference, so it
+/// has to be owned by the host. Defaulted environments are cheap to build and can be
+/// changed through the public fields, for example to select the spec.
+#[derive(Clone, Debug, Default)]
+pub struct EmptyHost<BLOCK, TX, CFG>
+where
+    BLOCK: Block,
+    TX: Transaction,
+    CFG: Cfg,
+{
+    pub tx: TX,
+    pub block: BLOCK,
+    pub cfg: CFG,
+}
+
+impl<TX: Transaction, BLOCK: Block, CFG: Cfg> Host for EmptyHost<BLOCK, TX, CFG> {
+    type TX = TX;
+    type BLOCK = BLOCK;
+    type CFG = CFG;
+
+    #[inline]
+    fn tx(&self) -> &Self::TX {
+        &self.tx
+    }
+
+    #[inline]
+    fn block(&self) -> &Self::BLOCK {
+        &self.block
+    }
+
+    #[inline]
+    fn cfg(&self) -> &Self::CFG {
+        &self.cfg
+    }
+
+    #[inline]
+    fn load_account_delegated(&mut self, _address: Address) -> Option<AccountLoad> {
+        None
+    }
+
+    #[inline]
+    fn block_hash(&mut self, _number: u64) -> Option<B256> {
+        None
+    }
+
+    #[inline]
+    fn balance(&mut self, _address: Address) -> Option<StateLoad<U256>> {
+        None
+    }
+
+    #[inline]
+    fn code(&mut self, _address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+        None
+    }
+
+    #[inline]
+    fn code_hash(&mut self, _address: Address) -> Option<Eip7702CodeLoad<B256>> {
+        None
+    }
+
+    #[inline]
+    fn sload(&mut self, _address: Address, _index: U256) -> Option<StateLoad<U256>> {
+        None
+    }
+
+    #[inline]
+    fn sstore(
+        &mut self,
+        _address: Address,
+        _index: U256,
+        _value: U256,
+    ) -> Option<StateLoad<SStoreResult>> {
+        None
+    }
+
+    #[inline]
+    fn tload(&mut self, _address: Address, _index: U256) -> U256 {
+        U256::ZERO
+    }
+
+    #[inline]
+    fn tstore(&mut self, _address: Address, _index: U256, _value: U256) {}
+
+    #[inline]
+    fn log(&mut self, _log: Log) {}
+
+    #[inline]
+    fn selfdestruct(
+        &mut self,
+        _address: Address,
+        _target: Address,
+    ) -> Option<StateLoad<SelfDestructResult>> {
+        None
+    }
+
+    #[inline]
+    fn checkpoint(&mut self) -> JournalCheckpoint {
+        // There is no state to revert.
+        JournalCheckpoint {
+            log_i: 0,
+            journal_i: 0,
+        }
+    }
+
+    #[inline]
+    fn checkpoint_commit(&mut self) {}
+
+    #[inline]
+    fn checkpoint_revert(&mut self, _checkpoint: JournalCheckpoint) {}
+}
@@ -54,11 +54,36 @@ impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
         matches!(self, Self::Success { .. })
     }
 
+    /// Returns true if execution result is a Revert.
+    ///
+    /// Reverted execution returns output and unspent gas, unlike a Halt.
+    pub fn is_revert(&self) -> bool {
+        matches!(self, Self::Revert { .. })
+    }
+
     /// Returns true if execution result is a Halt.
     pub fn is_halt(&self) -> bool {
         matches!(self, Self::Halt { .. })
     }
 
+    /// Returns the [SuccessReason] if the execution was successful.
+    pub fn success_reason(&self) -> Option<SuccessReason> {
+        match self {
+            Self::Success { reason, .. } => Some(*reason),
+            _ => None,
+        }
+    }
+
+    /// Returns the halt reason if the execution was halted.
+    ///
+    /// Returns [`None`] for successful and reverted execution.
+    pub fn halt_reason(&self) -> Option<&HaltReasonT> {
+        match self {
+            Self::Halt { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+
     /// Returns the output data of the execution.
     ///
     /// Returns [`None`] if the execution was halted.
@@ -469,6 +494,10 @@ pub enum HaltReason {
     SubRoutineStackOverflow,
     /// Check for target address validity is only done inside subcall.
     InvalidEXTCALLTarget,
+    /// Instruction limit of the interpreter run was exhausted.
+    OutOfInstructions,
+    /// Sub call returned more gas than the caller had spent.
+    GasUnderflow,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -487,4 +516,41 @@ pub enum OutOfGasError {
     InvalidOperand,
     // When performing SSTORE the gasleft is less than or equal to 2300
     ReentrancySentry,
+    // Single instruction spent more gas than the configured limit
+    OpcodeGasLimit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn execution_result_predicates() {
+        let success = ExecutionResult::<HaltReason>::Success {
+            reason: SuccessReason::Return,
+            gas_used: 21_000,
+            gas_refunded: 0,
+            logs: Vec::new(),
+            output: Output::Call(Bytes::new()),
+        };
+        assert!(success.is_success() && !success.is_revert() && !success.is_halt());
+        assert_eq!(success.success_reason(), Some(SuccessReason::Return));
+        assert_eq!(success.halt_reason(), None);
+
+        let revert = ExecutionResult::<HaltReason>::Revert {
+            gas_used: 21_000,
+            output: Bytes::new(),
+        };
+        assert!(!revert.is_success() && revert.is_revert() && !revert.is_halt());
+        assert_eq!(revert.success_reason(), None);
+        assert_eq!(revert.halt_reason(), None);
+
+        let halt = ExecutionResult::Halt {
+            reason: HaltReason::OpcodeNotFound,
+            gas_used: 21_000,
+        };
+        assert!(!halt.is_success() && !halt.is_revert() && halt.is_halt());
+        assert_eq!(halt.success_reason(), None);
+        assert_eq!(halt.halt_reason(), Some(&HaltReason::OpcodeNotFound));
+    }
 }
@@ -1,6 +1,7 @@
 pub use context_interface::Cfg;
 
-use interpreter::MAX_CODE_SIZE;
+use core::num::NonZeroU64;
+use interpreter::{gas::MEMORY_QUAD_COEFF_DIV, MAX_CODE_SIZE};
 use specification::hardfork::SpecId;
 
 /// EVM configuration
@@ -24,6 +25,17 @@ pub struct CfgEnv<SPEC: Into<SpecId> = SpecId> {
     pub limit_contract_code_size: Option<usize>,
     /// Skips the nonce validation against the account's nonce
     pub disable_nonce_check: bool,
+    /// Halts on `PUSH` instructions whose immediate extends past the end of legacy bytecode
+    ///
+    /// By default missing bytes are read as zeros. Useful for fuzzing.
+    pub strict_push: bool,
+    /// Denominator of the quadratic part of the memory expansion cost
+    ///
+    /// Chains with custom memory pricing can change it.
+    ///
+    /// By default, it is set to [`MEMORY_QUAD_COEFF_DIV`] (`512`).
+    #[cfg_attr(feature = "serde", serde(default = "default_memory_quad_coeff_div"))]
+    pub memory_quad_coeff_div: NonZeroU64,
     /// A hard memory limit in bytes beyond which
     /// [OutOfGasError::Memory][context_interface::result::OutOfGasError::Memory] cannot be resized.
     ///
@@ -138,6 +150,14 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
         self.disable_nonce_check
     }
 
+    fn is_strict_push_enabled(&self) -> bool {
+        self.strict_push
+    }
+
+    fn memory_quad_coeff_div(&self) -> NonZeroU64 {
+        self.memory_quad_coeff_div
+    }
+
     fn is_base_fee_check_disabled(&self) -> bool {
         cfg_if::cfg_if! {
             if #[cfg(feature = "optional_no_base_fee")] {
@@ -149,6 +169,10 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
     }
 }
 
+const fn default_memory_quad_coeff_div() -> NonZeroU64 {
+    NonZeroU64::new(MEMORY_QUAD_COEFF_DIV).unwrap()
+}
+
 impl Default for CfgEnv {
     fn default() -> Self {
         Self {
@@ -156,6 +180,8 @@ impl Default for CfgEnv {
             limit_contract_code_size: None,
             spec: SpecId::PRAGUE,
             disable_nonce_check: false,
+            strict_push: false,
+            memory_quad_coeff_div: default_memory_quad_coeff_div(),
             #[cfg(feature = "memory_limit")]
             memory_limit: (1 << 32) - 1,
             #[cfg(feature = "optional_balance_check")]
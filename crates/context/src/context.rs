@@ -2,7 +2,7 @@ use crate::{block::BlockEnv, cfg::CfgEnv, journaled_state::JournaledState, tx::T
 use bytecode::{Bytecode, EOF_MAGIC_BYTES, EOF_MAGIC_HASH};
 use context_interface::{
     block::BlockSetter,
-    journaled_state::{AccountLoad, Eip7702CodeLoad},
+    journaled_state::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint},
     result::EVMError,
     transaction::TransactionSetter,
     Block, BlockGetter, Cfg, CfgGetter, DatabaseGetter, ErrorGetter, Journal, JournalStateGetter,
@@ -486,6 +486,18 @@ where
             .map_err(|e| self.error = Err(e))
             .ok()
     }
+
+    fn checkpoint(&mut self) -> JournalCheckpoint {
+        self.journaled_state.checkpoint()
+    }
+
+    fn checkpoint_commit(&mut self) {
+        self.journaled_state.checkpoint_commit()
+    }
+
+    fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
+        self.journaled_state.checkpoint_revert(checkpoint)
+    }
 }
 
 impl<BLOCK, TX, CFG: Cfg, DB: Database, JOURNAL: Journal<Database = DB>, CHAIN> CfgGetter
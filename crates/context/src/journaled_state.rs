@@ -390,7 +390,7 @@ impl<DB: Database> JournaledState<DB> {
     ///
     /// There are few steps done:
     /// 1. Make created account warm loaded (AccessList) and this should
-    ///     be done before subroutine checkpoint is created.
+    ///    be done before subroutine checkpoint is created.
     /// 2. Check if there is collision of newly created account with existing one.
     /// 3. Mark created account as created.
     /// 4. Add fund to created account
@@ -1030,3 +1030,105 @@ pub enum JournalEntry {
     /// Revert: Revert to previous bytecode.
     CodeChange { address: Address },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::InMemoryDB;
+    use primitives::address;
+    use state::AccountInfo;
+
+    const CALLER: Address = address!("1000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("2000000000000000000000000000000000000002");
+    const TARGET: Address = address!("3000000000000000000000000000000000000003");
+
+    fn journal(spec: SpecId) -> JournaledState<InMemoryDB> {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(100)));
+        db.insert_account_info(CONTRACT, AccountInfo::from_balance(U256::from(100)));
+        let mut journal = JournaledState::new(spec, db);
+        journal.load_account(CALLER).unwrap();
+        journal.load_account(TARGET).unwrap();
+        journal
+    }
+
+    #[test]
+    fn selfdestruct_created_in_same_tx() {
+        let mut journal = journal(CANCUN);
+        let created = address!("4000000000000000000000000000000000000004");
+        journal.load_account(created).unwrap();
+        journal
+            .create_account_checkpoint(CALLER, created, U256::from(50), CANCUN)
+            .unwrap();
+
+        journal.selfdestruct(created, TARGET).unwrap();
+        assert!(journal.account(created).is_selfdestructed());
+        assert_eq!(journal.account(created).info.balance, U256::ZERO);
+        assert_eq!(journal.account(TARGET).info.balance, U256::from(50));
+    }
+
+    #[test]
+    fn selfdestruct_pre_existing_cancun() {
+        let mut journal = journal(CANCUN);
+        journal.load_account(CONTRACT).unwrap();
+
+        journal.selfdestruct(CONTRACT, TARGET).unwrap();
+        assert!(!journal.account(CONTRACT).is_selfdestructed());
+        assert_eq!(journal.account(CONTRACT).info.balance, U256::ZERO);
+        assert_eq!(journal.account(TARGET).info.balance, U256::from(100));
+    }
+
+    #[test]
+    fn selfdestruct_pre_existing_pre_cancun() {
+        let mut journal = journal(SHANGHAI);
+        journal.load_account(CONTRACT).unwrap();
+
+        journal.selfdestruct(CONTRACT, TARGET).unwrap();
+        assert!(journal.account(CONTRACT).is_selfdestructed());
+        assert_eq!(journal.account(CONTRACT).info.balance, U256::ZERO);
+        assert_eq!(journal.account(TARGET).info.balance, U256::from(100));
+    }
+
+    #[test]
+    fn checkpoint_revert_restores_state() {
+        let mut journal = journal(CANCUN);
+        journal.load_account(CONTRACT).unwrap();
+        let key = U256::from(1);
+        journal.sstore(CONTRACT, key, U256::from(1)).unwrap();
+
+        let checkpoint = journal.checkpoint();
+        journal.sstore(CONTRACT, key, U256::from(2)).unwrap();
+        journal.tstore(CONTRACT, key, U256::from(3));
+        journal.log(Log::default());
+        journal
+            .transfer(&CALLER, &CONTRACT, U256::from(10))
+            .unwrap();
+        journal.inc_nonce(CALLER);
+        journal.checkpoint_revert(checkpoint);
+
+        assert_eq!(journal.sload(CONTRACT, key).unwrap().data, U256::from(1));
+        assert_eq!(journal.tload(CONTRACT, key), U256::ZERO);
+        assert!(journal.logs.is_empty());
+        assert_eq!(journal.account(CALLER).info.balance, U256::from(100));
+        assert_eq!(journal.account(CALLER).info.nonce, 0);
+        assert_eq!(journal.account(CONTRACT).info.balance, U256::from(100));
+        assert_eq!(journal.depth, 0);
+    }
+
+    #[test]
+    fn checkpoint_commit_keeps_state() {
+        let mut journal = journal(CANCUN);
+        journal.load_account(CONTRACT).unwrap();
+        let key = U256::from(1);
+
+        let outer = journal.checkpoint();
+        journal.checkpoint();
+        journal.sstore(CONTRACT, key, U256::from(2)).unwrap();
+        journal.checkpoint_commit();
+        assert_eq!(journal.sload(CONTRACT, key).unwrap().data, U256::from(2));
+
+        // Committed changes are still reverted together with the outer checkpoint.
+        journal.checkpoint_revert(outer);
+        assert_eq!(journal.sload(CONTRACT, key).unwrap().data, U256::ZERO);
+    }
+}
@@ -4,12 +4,14 @@
 mod alloydb;
 
 pub mod in_memory_db;
+pub mod proof_db;
 pub mod states;
 
 #[cfg(feature = "alloydb")]
 pub use alloydb::{AlloyDB, BlockId};
 
 pub use in_memory_db::*;
+pub use proof_db::{ProofBackedDB, ProofDBError, ProofError};
 pub use states::{
     AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
     OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
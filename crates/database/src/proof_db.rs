@@ -0,0 +1,499 @@
+//! Database that serves storage from verified Merkle-Patricia trie proofs.
+use alloy_rlp::{Decodable, Header, PayloadView};
+use core::fmt;
+use database_interface::{DBErrorMarker, Database};
+use primitives::{b256, keccak256, Address, Bytes, HashMap, B256, U256};
+use state::{AccountInfo, Bytecode};
+use std::vec::Vec;
+
+/// Root hash of an empty trie, `keccak256(rlp(""))`.
+pub const EMPTY_ROOT_HASH: B256 =
+    b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+
+/// Error returned when a trie proof can't be verified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    /// Node is not valid RLP.
+    Rlp(alloy_rlp::Error),
+    /// Node is not a branch, extension or leaf node.
+    InvalidNode,
+    /// Node doesn't match the hash referenced by its parent.
+    NodeMismatch,
+    /// Proof ended before reaching the value.
+    Incomplete,
+    /// Proof contains nodes after the value was reached.
+    UnexpectedNode,
+}
+
+impl From<alloy_rlp::Error> for ProofError {
+    fn from(e: alloy_rlp::Error) -> Self {
+        Self::Rlp(e)
+    }
+}
+
+impl core::error::Error for ProofError {}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rlp(e) => write!(f, "invalid RLP: {e}"),
+            Self::InvalidNode => f.write_str("invalid trie node"),
+            Self::NodeMismatch => f.write_str("trie node does not match its hash"),
+            Self::Incomplete => f.write_str("proof is incomplete"),
+            Self::UnexpectedNode => f.write_str("proof has nodes after the value"),
+        }
+    }
+}
+
+/// Error of [ProofBackedDB].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofDBError<E> {
+    /// Error of the wrapped database.
+    Database(E),
+    /// No account proof was provided for the address.
+    MissingAccountProof(Address),
+    /// No proof was provided for the storage slot.
+    MissingProof { address: Address, slot: U256 },
+    /// Provided proof is invalid.
+    InvalidProof(ProofError),
+}
+
+impl<E> DBErrorMarker for ProofDBError<E> {}
+
+impl<E: core::error::Error + 'static> core::error::Error for ProofDBError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Database(e) => Some(e),
+            Self::InvalidProof(e) => Some(e),
+            Self::MissingAccountProof(_) | Self::MissingProof { .. } => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ProofDBError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "database error: {e}"),
+            Self::MissingAccountProof(address) => {
+                write!(f, "missing account proof for {address}")
+            }
+            Self::MissingProof { address, slot } => {
+                write!(f, "missing storage proof for {address} slot {slot}")
+            }
+            Self::InvalidProof(e) => write!(f, "invalid proof: {e}"),
+        }
+    }
+}
+
+/// Account proof and proofs of the storage slots of an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageProofs {
+    /// Trie nodes from the state root to the account, as returned by `eth_getProof`.
+    pub account_proof: Vec<Bytes>,
+    /// Trie nodes from the storage root to the slot, as returned by `eth_getProof`.
+    pub slots: HashMap<U256, Vec<Bytes>>,
+}
+
+/// A [Database] that serves storage only from proofs verified against a state root.
+///
+/// The storage root of an account is taken from its account proof, and storage proofs
+/// are verified against it. Accounts, code and block hashes are loaded from the wrapped
+/// database.
+#[derive(Clone, Debug)]
+pub struct ProofBackedDB<DB> {
+    /// Wrapped database.
+    pub db: DB,
+    /// State root the account proofs are verified against.
+    pub state_root: B256,
+    /// Proofs per account.
+    pub proofs: HashMap<Address, StorageProofs>,
+}
+
+impl<DB> ProofBackedDB<DB> {
+    /// Creates a new database without any proofs.
+    pub fn new(db: DB, state_root: B256) -> Self {
+        Self {
+            db,
+            state_root,
+            proofs: HashMap::default(),
+        }
+    }
+
+    /// Inserts the proof of the account against the state root.
+    pub fn insert_account_proof(&mut self, address: Address, proof: Vec<Bytes>) {
+        self.proofs.entry(address).or_default().account_proof = proof;
+    }
+
+    /// Inserts a proof of `slot` against the storage root of the account.
+    pub fn insert_storage_proof(&mut self, address: Address, slot: U256, proof: Vec<Bytes>) {
+        self.proofs
+            .entry(address)
+            .or_default()
+            .slots
+            .insert(slot, proof);
+    }
+}
+
+impl<DB: Database> Database for ProofBackedDB<DB> {
+    type Error = ProofDBError<DB::Error>;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address).map_err(ProofDBError::Database)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db
+            .code_by_hash(code_hash)
+            .map_err(ProofDBError::Database)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let Some(proofs) = self
+            .proofs
+            .get(&address)
+            .filter(|proofs| !proofs.account_proof.is_empty())
+        else {
+            return Err(ProofDBError::MissingAccountProof(address));
+        };
+        let Some(proof) = proofs.slots.get(&index) else {
+            return Err(ProofDBError::MissingProof {
+                address,
+                slot: index,
+            });
+        };
+        let storage_root = verify_account_proof(self.state_root, address, &proofs.account_proof)
+            .map_err(ProofDBError::InvalidProof)?;
+        verify_storage_proof(storage_root, index, proof).map_err(ProofDBError::InvalidProof)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash(number).map_err(ProofDBError::Database)
+    }
+}
+
+/// Verifies a proof of the account at `address` against `state_root` and returns its
+/// storage root.
+///
+/// Proof of exclusion returns [EMPTY_ROOT_HASH].
+pub fn verify_account_proof(
+    state_root: B256,
+    address: Address,
+    proof: &[Bytes],
+) -> Result<B256, ProofError> {
+    match verify_proof(state_root, &nibbles(keccak256(address)), proof)? {
+        Some(mut value) => {
+            let mut account = Header::decode_bytes(&mut value, false)?;
+            let mut fields = Header::decode_bytes(&mut account, true)?;
+            let _nonce = u64::decode(&mut fields)?;
+            let _balance = U256::decode(&mut fields)?;
+            Ok(B256::decode(&mut fields)?)
+        }
+        None => Ok(EMPTY_ROOT_HASH),
+    }
+}
+
+/// Verifies a proof of `slot` against `storage_root` and returns its value.
+///
+/// Proof of exclusion returns zero, as does an empty proof for [EMPTY_ROOT_HASH].
+pub fn verify_storage_proof(
+    storage_root: B256,
+    slot: U256,
+    proof: &[Bytes],
+) -> Result<U256, ProofError> {
+    let path = nibbles(keccak256(slot.to_be_bytes::<32>()));
+    match verify_proof(storage_root, &path, proof)? {
+        Some(mut value) => {
+            let mut value = Header::decode_bytes(&mut value, false)?;
+            Ok(U256::decode(&mut value)?)
+        }
+        None => Ok(U256::ZERO),
+    }
+}
+
+/// Splits a hashed key into the nibbles of its trie path.
+fn nibbles(key: B256) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0F]).collect()
+}
+
+/// Reference to a child node.
+enum NodeRef<'a> {
+    Hash(B256),
+    Inline(&'a [u8]),
+}
+
+/// Walks the proof along the nibble `path` and returns the RLP encoded leaf value.
+fn verify_proof<'a>(
+    root: B256,
+    mut path: &[u8],
+    proof: &'a [Bytes],
+) -> Result<Option<&'a [u8]>, ProofError> {
+    if proof.is_empty() && root == EMPTY_ROOT_HASH {
+        return Ok(None);
+    }
+
+    let mut nodes = proof.iter();
+    let mut expected = NodeRef::Hash(root);
+    let value = loop {
+        let node: &[u8] = match expected {
+            NodeRef::Hash(hash) => {
+                let node = nodes.next().ok_or(ProofError::Incomplete)?;
+                if keccak256(node) != hash {
+                    return Err(ProofError::NodeMismatch);
+                }
+                &node[..]
+            }
+            NodeRef::Inline(node) => node,
+        };
+
+        let mut buf = node;
+        let PayloadView::List(items) = Header::decode_raw(&mut buf)? else {
+            return Err(ProofError::InvalidNode);
+        };
+        let child = match items[..] {
+            [ref children @ .., value] if children.len() == 16 => match path.split_first() {
+                Some((&nibble, rest)) => {
+                    path = rest;
+                    children[nibble as usize]
+                }
+                None => break non_empty(value)?,
+            },
+            [key, value] => {
+                let (is_leaf, key) = decode_path(key)?;
+                if is_leaf {
+                    break (path == key).then_some(value);
+                }
+                let Some(rest) = path.strip_prefix(key.as_slice()) else {
+                    break None;
+                };
+                path = rest;
+                value
+            }
+            _ => return Err(ProofError::InvalidNode),
+        };
+
+        expected = match child_ref(child)? {
+            Some(child) => child,
+            None => break None,
+        };
+    };
+
+    if nodes.next().is_some() {
+        return Err(ProofError::UnexpectedNode);
+    }
+    Ok(value)
+}
+
+/// Returns `None` for an empty string item.
+fn non_empty(item: &[u8]) -> Result<Option<&[u8]>, ProofError> {
+    let payload = Header::decode_bytes(&mut &item[..], false)?;
+    Ok((!payload.is_empty()).then_some(item))
+}
+
+/// Decodes a child reference that is either a hash, an embedded node or empty.
+fn child_ref(item: &[u8]) -> Result<Option<NodeRef<'_>>, ProofError> {
+    let header = Header::decode(&mut &item[..])?;
+    if header.list {
+        return Ok(Some(NodeRef::Inline(item)));
+    }
+    match Header::decode_bytes(&mut &item[..], false)? {
+        [] => Ok(None),
+        hash if hash.len() == 32 => Ok(Some(NodeRef::Hash(B256::from_slice(hash)))),
+        _ => Err(ProofError::InvalidNode),
+    }
+}
+
+/// Decodes hex-prefix encoded path into leaf flag and nibbles.
+fn decode_path(item: &[u8]) -> Result<(bool, Vec<u8>), ProofError> {
+    let encoded = Header::decode_bytes(&mut &item[..], false)?;
+    let (&first, rest) = encoded.split_first().ok_or(ProofError::InvalidNode)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(ProofError::InvalidNode);
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0F);
+    }
+    nibbles.extend(rest.iter().flat_map(|b| [b >> 4, b & 0x0F]));
+    Ok((flag & 2 == 2, nibbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use database_interface::EmptyDB;
+    use primitives::address;
+    use std::vec;
+
+    fn rlp_list(items: &[&[u8]]) -> Bytes {
+        let payload = items.concat();
+        let mut out = Vec::new();
+        Header {
+            list: true,
+            payload_length: payload.len(),
+        }
+        .encode(&mut out);
+        out.extend(payload);
+        out.into()
+    }
+
+    fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        bytes.encode(&mut out);
+        out
+    }
+
+    fn leaf(nibbles: &[u8], value: U256) -> Bytes {
+        leaf_node(nibbles, &alloy_rlp::encode(value))
+    }
+
+    /// Leaf of an account with the given storage root.
+    fn account_leaf(nibbles: &[u8], storage_root: B256) -> Bytes {
+        let account = rlp_list(&[
+            &alloy_rlp::encode(1u64),
+            &alloy_rlp::encode(U256::from(100)),
+            &alloy_rlp::encode(storage_root),
+            &alloy_rlp::encode(keccak256([])),
+        ]);
+        leaf_node(nibbles, &account)
+    }
+
+    fn leaf_node(nibbles: &[u8], value: &[u8]) -> Bytes {
+        let mut path = if nibbles.len() % 2 == 1 {
+            vec![0x30 | nibbles[0]]
+        } else {
+            vec![0x20]
+        };
+        path.extend(
+            nibbles[nibbles.len() % 2..]
+                .chunks(2)
+                .map(|c| c[0] << 4 | c[1]),
+        );
+        rlp_list(&[&rlp_bytes(&path), &rlp_bytes(value)])
+    }
+
+    fn slot_path(slot: U256) -> Vec<u8> {
+        nibbles(keccak256(slot.to_be_bytes::<32>()))
+    }
+
+    #[test]
+    fn single_leaf_proof() {
+        let slot = U256::from(1);
+        let node = leaf(&slot_path(slot), U256::from(42));
+        let root = keccak256(&node);
+
+        assert_eq!(
+            verify_storage_proof(root, slot, core::slice::from_ref(&node)),
+            Ok(U256::from(42))
+        );
+        // Other slot is proven to be absent.
+        assert_eq!(
+            verify_storage_proof(root, U256::from(2), &[node]),
+            Ok(U256::ZERO)
+        );
+        assert_eq!(
+            verify_storage_proof(EMPTY_ROOT_HASH, slot, &[]),
+            Ok(U256::ZERO)
+        );
+    }
+
+    #[test]
+    fn branch_proof() {
+        let (a, b) = (U256::from(0), U256::from(1));
+        let (path_a, path_b) = (slot_path(a), slot_path(b));
+        assert_ne!(path_a[0], path_b[0]);
+
+        let leaf_a = leaf(&path_a[1..], U256::from(0xAA));
+        let leaf_b = leaf(&path_b[1..], U256::from(0xBB));
+        let mut children = vec![rlp_bytes(&[]); 17];
+        children[path_a[0] as usize] = rlp_bytes(keccak256(&leaf_a).as_slice());
+        children[path_b[0] as usize] = rlp_bytes(keccak256(&leaf_b).as_slice());
+        let branch = rlp_list(&children.iter().map(Vec::as_slice).collect::<Vec<_>>());
+        let root = keccak256(&branch);
+
+        assert_eq!(
+            verify_storage_proof(root, a, &[branch.clone(), leaf_a.clone()]),
+            Ok(U256::from(0xAA))
+        );
+        assert_eq!(
+            verify_storage_proof(root, b, &[branch.clone(), leaf_b]),
+            Ok(U256::from(0xBB))
+        );
+        // Leaf of `a` doesn't match the hash referenced for `b`.
+        assert_eq!(
+            verify_storage_proof(root, b, &[branch.clone(), leaf_a.clone()]),
+            Err(ProofError::NodeMismatch)
+        );
+        assert_eq!(
+            verify_storage_proof(root, a, core::slice::from_ref(&branch)),
+            Err(ProofError::Incomplete)
+        );
+        assert_eq!(
+            verify_storage_proof(root, a, &[branch.clone(), leaf_a.clone(), leaf_a]),
+            Err(ProofError::UnexpectedNode)
+        );
+    }
+
+    #[test]
+    fn proof_backed_storage() {
+        let account = address!("0000000000000000000000000000000000000100");
+        let slot = U256::from(1);
+        let node = leaf(&slot_path(slot), U256::from(42));
+        let storage_root = keccak256(&node);
+        let account_node = account_leaf(&nibbles(keccak256(account)), storage_root);
+        let state_root = keccak256(&account_node);
+
+        let mut db = ProofBackedDB::new(EmptyDB::default(), state_root);
+        db.insert_storage_proof(account, slot, vec![node.clone()]);
+        assert_eq!(
+            db.storage(account, slot),
+            Err(ProofDBError::MissingAccountProof(account))
+        );
+
+        db.insert_account_proof(account, vec![account_node.clone()]);
+        assert_eq!(db.storage(account, slot), Ok(U256::from(42)));
+        assert_eq!(
+            db.storage(account, U256::from(2)),
+            Err(ProofDBError::MissingProof {
+                address: account,
+                slot: U256::from(2)
+            })
+        );
+
+        // Tampered value no longer matches the storage root.
+        let tampered = leaf(&slot_path(slot), U256::from(43));
+        db.insert_storage_proof(account, slot, vec![tampered.clone()]);
+        assert_eq!(
+            db.storage(account, slot),
+            Err(ProofDBError::InvalidProof(ProofError::NodeMismatch))
+        );
+
+        // Storage root of the tampered value is not the one committed to by the state root.
+        let tampered_account = account_leaf(&nibbles(keccak256(account)), keccak256(&tampered));
+        db.insert_account_proof(account, vec![tampered_account]);
+        assert_eq!(
+            db.storage(account, slot),
+            Err(ProofDBError::InvalidProof(ProofError::NodeMismatch))
+        );
+    }
+
+    #[test]
+    fn account_proof() {
+        let account = address!("0000000000000000000000000000000000000100");
+        let storage_root = B256::repeat_byte(0x11);
+        let node = account_leaf(&nibbles(keccak256(account)), storage_root);
+        let state_root = keccak256(&node);
+
+        assert_eq!(
+            verify_account_proof(state_root, account, core::slice::from_ref(&node)),
+            Ok(storage_root)
+        );
+        // Other account is proven to be absent.
+        assert_eq!(
+            verify_account_proof(state_root, Address::ZERO, &[node]),
+            Ok(EMPTY_ROOT_HASH)
+        );
+    }
+}
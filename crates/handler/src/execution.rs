@@ -8,7 +8,7 @@ use handler_interface::{util::FrameOrFrameResult, ExecutionHandler, Frame as Fra
 use interpreter::{
     interpreter::{EthInstructionProvider, EthInterpreter},
     CallInputs, CallScheme, CallValue, CreateInputs, CreateScheme, EOFCreateInputs, EOFCreateKind,
-    FrameInput, Gas,
+    FrameInput, Gas, InstructionResult,
 };
 use primitives::TxKind;
 use specification::hardfork::SpecId;
@@ -92,7 +92,7 @@ where
         context: &mut Self::Context,
         mut frame_result: <Self::Frame as FrameTrait>::FrameResult,
     ) -> Result<Self::ExecResult, Self::Error> {
-        let instruction_result = frame_result.interpreter_result().result;
+        let mut instruction_result = frame_result.interpreter_result().result;
         let gas = frame_result.gas_mut();
         let remaining = gas.remaining();
         let refunded = gas.refunded();
@@ -100,12 +100,13 @@ where
         // Spend the gas limit. Gas is reimbursed when the tx returns successfully.
         *gas = Gas::new_spent(context.tx().common_fields().gas_limit());
 
-        if instruction_result.is_ok_or_revert() {
-            gas.erase_cost(remaining);
+        if instruction_result.is_ok_or_revert() && !gas.erase_cost(remaining) {
+            instruction_result = InstructionResult::GasUnderflow;
+            frame_result.interpreter_result_mut().result = instruction_result;
         }
 
         if instruction_result.is_ok() {
-            gas.record_refund(refunded);
+            frame_result.gas_mut().record_refund(refunded);
         }
 
         Ok(frame_result)
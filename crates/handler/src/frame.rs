@@ -5,7 +5,7 @@ use context_interface::{
     BlockGetter, Cfg, CfgGetter, ErrorGetter, JournalStateGetter, JournalStateGetterDBError,
     Transaction, TransactionGetter,
 };
-use core::{cell::RefCell, cmp::min};
+use core::cell::RefCell;
 use handler_interface::{Frame, FrameOrResultGen, PrecompileProvider};
 use interpreter::{
     gas,
@@ -194,7 +194,9 @@ where
                     false,
                     context.cfg().spec().into(),
                     inputs.gas_limit,
-                ),
+                )
+                .with_depth(depth)
+                .with_memory_quad_coeff_div(context.cfg().memory_quad_coeff_div()),
                 checkpoint,
                 precompile,
                 instructions,
@@ -301,7 +303,9 @@ where
                 false,
                 spec,
                 inputs.gas_limit,
-            ),
+            )
+            .with_depth(depth)
+            .with_memory_quad_coeff_div(context.cfg().memory_quad_coeff_div()),
             checkpoint,
             precompile,
             instructions,
@@ -418,7 +422,9 @@ where
                 true,
                 spec,
                 inputs.gas_limit,
-            ),
+            )
+            .with_depth(depth)
+            .with_memory_quad_coeff_div(context.cfg().memory_quad_coeff_div()),
             checkpoint,
             precompile,
             instructions,
@@ -505,8 +511,11 @@ where
     ) -> Result<FrameOrResultGen<Self::FrameInit, Self::FrameResult>, Self::Error> {
         let spec = context.cfg().spec().into();
 
-        // Run interpreter
-        let next_action = self.interpreter.run(self.instructions.table(), context);
+        // Run interpreter, unless inserting the last sub call result already halted it.
+        let next_action = match self.interpreter.control.take_next_action() {
+            InterpreterAction::None => self.interpreter.run(self.instructions.table(), context),
+            action => action,
+        };
 
         let mut interpreter_result = match next_action {
             InterpreterAction::NewFrame(new_frame) => {
@@ -580,15 +589,12 @@ where
             FrameResult::Call(outcome) => {
                 let out_gas = outcome.gas();
                 let ins_result = *outcome.instruction_result();
-                let returned_len = outcome.result.output.len();
 
                 let interpreter = &mut self.interpreter;
-                let mem_length = outcome.memory_length();
                 let mem_start = outcome.memory_start();
+                let mem_output = outcome.memory_output();
                 *interpreter.return_data.buffer_mut() = outcome.result.output;
 
-                let target_len = min(mem_length, returned_len);
-
                 if ins_result == InstructionResult::FatalExternalError {
                     panic!("Fatal external error in insert_call_outcome");
                 }
@@ -606,20 +612,37 @@ where
                         U256::ZERO
                     }
                 };
+                // Return unspend gas.
+                if ins_result.is_ok_or_revert()
+                    && !return_sub_call_gas(interpreter, out_gas.remaining())
+                {
+                    return Ok(());
+                }
+
                 // Safe to push without stack limit check
                 let _ = interpreter.stack.push(item);
 
-                // Return unspend gas.
                 if ins_result.is_ok_or_revert() {
-                    interpreter.control.gas().erase_cost(out_gas.remaining());
-                    self.memory
-                        .borrow_mut()
-                        .set(mem_start, &interpreter.return_data.buffer()[..target_len]);
+                    if let Err(result) = self.memory.borrow_mut().copy_from_slice_checked(
+                        mem_start,
+                        &mem_output,
+                        mem_output.len(),
+                    ) {
+                        halt(interpreter, result);
+                        return Ok(());
+                    }
                 }
 
                 if ins_result.is_ok() {
                     interpreter.control.gas().record_refund(out_gas.refunded());
                 }
+
+                if let Some(mut breakdown) = interpreter.take_call_gas() {
+                    if ins_result.is_ok_or_revert() {
+                        breakdown.returned = out_gas.remaining();
+                    }
+                    context.trace_call_gas(&breakdown);
+                }
             }
             FrameResult::Create(outcome) => {
                 let instruction_result = *outcome.instruction_result();
@@ -640,10 +663,12 @@ where
                     "Fatal external error in insert_eofcreate_outcome"
                 );
 
-                let this_gas = interpreter.control.gas();
-                if instruction_result.is_ok_or_revert() {
-                    this_gas.erase_cost(outcome.gas().remaining());
+                if instruction_result.is_ok_or_revert()
+                    && !return_sub_call_gas(interpreter, outcome.gas().remaining())
+                {
+                    return Ok(());
                 }
+                let this_gas = interpreter.control.gas();
 
                 let stack_item = if instruction_result.is_ok() {
                     this_gas.record_refund(outcome.gas().refunded());
@@ -672,10 +697,12 @@ where
                     "Fatal external error in insert_eofcreate_outcome"
                 );
 
-                let this_gas = interpreter.control.gas();
-                if instruction_result.is_ok_or_revert() {
-                    this_gas.erase_cost(outcome.gas().remaining());
+                if instruction_result.is_ok_or_revert()
+                    && !return_sub_call_gas(interpreter, outcome.gas().remaining())
+                {
+                    return Ok(());
                 }
+                let this_gas = interpreter.control.gas();
 
                 let stack_item = if instruction_result.is_ok() {
                     this_gas.record_refund(outcome.gas().refunded());
@@ -791,6 +818,39 @@ pub fn return_eofcreate<JOURNAL: Journal>(
     journal.set_code(address, Bytecode::Eof(Arc::new(bytecode)));
 }
 
+/// Returns the unspent gas of a sub call to the interpreter.
+///
+/// If more gas is returned than the interpreter has spent, halts the interpreter with
+/// [`InstructionResult::GasUnderflow`] and returns `false`.
+pub(crate) fn return_sub_call_gas<IW: InterpreterTypes>(
+    interpreter: &mut Interpreter<IW>,
+    returned: u64,
+) -> bool {
+    if interpreter.control.gas().erase_cost(returned) {
+        return true;
+    }
+    halt(interpreter, InstructionResult::GasUnderflow);
+    false
+}
+
+/// Halts the interpreter with `result` while a sub call result is inserted.
+///
+/// The halt is set as the next action so that it is returned by the next run instead of
+/// being cleared when the run starts.
+fn halt<IW: InterpreterTypes>(interpreter: &mut Interpreter<IW>, result: InstructionResult) {
+    let gas = *interpreter.control.gas();
+    interpreter.control.set_next_action(
+        InterpreterAction::Return {
+            result: InterpreterResult {
+                result,
+                output: Bytes::new(),
+                gas,
+            },
+        },
+        result,
+    );
+}
+
 pub trait EthFrameContext<ERROR>:
     TransactionGetter + Host + ErrorGetter<Error = ERROR> + BlockGetter + JournalStateGetter + CfgGetter
 {
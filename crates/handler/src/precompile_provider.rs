@@ -53,9 +53,12 @@ where
         };
 
         match (*precompile)(bytes, gas_limit) {
+            // Precompiles check required gas before executing, but don't trust
+            // the output if it spent more than it was given.
+            Ok(output) if !result.gas.record_cost(output.gas_used) => {
+                result.result = InstructionResult::PrecompileOOG;
+            }
             Ok(output) => {
-                let underflow = result.gas.record_cost(output.gas_used);
-                assert!(underflow, "Gas underflow is not possible");
                 result.result = InstructionResult::Return;
                 result.output = output.bytes;
             }
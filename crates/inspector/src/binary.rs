@@ -0,0 +1,222 @@
+//! Compact binary tracer.
+use crate::Inspector;
+use derive_where::derive_where;
+use revm::interpreter::{
+    interpreter_types::{Jumps, LoopControl},
+    Interpreter, InterpreterTypes,
+};
+use std::io::{self, Read, Write};
+
+/// Maximum length of an encoded record: two 10-byte varints and an opcode.
+const MAX_RECORD_LEN: usize = 21;
+
+/// Single step of a binary trace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BinaryStep {
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Executed opcode.
+    pub opcode: u8,
+    /// Remaining gas before the instruction.
+    pub gas: u64,
+}
+
+impl BinaryStep {
+    /// Encodes the step as a length-prefixed record of varint `pc`, opcode byte and
+    /// varint `gas`.
+    pub fn encode(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut record = [0u8; MAX_RECORD_LEN + 1];
+        let mut len = 1;
+        len += write_varint(&mut record[len..], self.pc as u64);
+        record[len] = self.opcode;
+        len += 1;
+        len += write_varint(&mut record[len..], self.gas);
+        record[0] = (len - 1) as u8;
+        out.write_all(&record[..len])
+    }
+
+    /// Decodes the next record, returning `None` at the end of input.
+    pub fn decode(input: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut len = [0u8; 1];
+        if input.read(&mut len)? == 0 {
+            return Ok(None);
+        }
+        let len = len[0] as usize;
+        if len > MAX_RECORD_LEN {
+            return Err(invalid_data("record too long"));
+        }
+        let mut record = [0u8; MAX_RECORD_LEN];
+        input.read_exact(&mut record[..len])?;
+
+        let mut record = &record[..len];
+        let pc = read_varint(&mut record)?;
+        let (&opcode, mut record) = record
+            .split_first()
+            .ok_or_else(|| invalid_data("missing opcode"))?;
+        let gas = read_varint(&mut record)?;
+        if !record.is_empty() {
+            return Err(invalid_data("trailing record bytes"));
+        }
+        let pc = usize::try_from(pc).map_err(|_| invalid_data("pc overflow"))?;
+        Ok(Some(Self { pc, opcode, gas }))
+    }
+}
+
+/// Reads all steps written by [BinaryTracer].
+pub fn read_binary_trace(mut input: impl Read) -> io::Result<Vec<BinaryStep>> {
+    let mut steps = Vec::new();
+    while let Some(step) = BinaryStep::decode(&mut input)? {
+        steps.push(step);
+    }
+    Ok(steps)
+}
+
+fn write_varint(out: &mut [u8], mut value: u64) -> usize {
+    let mut i = 0;
+    while value >= 0x80 {
+        out[i] = value as u8 | 0x80;
+        value >>= 7;
+        i += 1;
+    }
+    out[i] = value as u8;
+    i + 1
+}
+
+fn read_varint(input: &mut &[u8]) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input
+            .split_first()
+            .ok_or_else(|| invalid_data("truncated varint"))?;
+        *input = rest;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("varint overflow"))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// [Inspector] that writes a [BinaryStep] record for every executed instruction.
+///
+/// Use [read_binary_trace] to decode the output.
+#[derive_where(Debug; CTX, INTR)]
+pub struct BinaryTracer<CTX, INTR> {
+    #[derive_where(skip)]
+    output: Box<dyn Write>,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> BinaryTracer<CTX, INTR> {
+    /// Creates a new tracer writing records to `output`.
+    pub fn new(output: Box<dyn Write>) -> Self {
+        Self {
+            output,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Sets the writer to use for the output.
+    pub fn set_writer(&mut self, writer: Box<dyn Write>) {
+        self.output = writer;
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for BinaryTracer<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        let step = BinaryStep {
+            pc: interp.bytecode.pc(),
+            opcode: interp.bytecode.opcode(),
+            gas: interp.control.gas().remaining(),
+        };
+        let _ = step.encode(&mut self.output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{ADD, PUSH1, STOP},
+        interpreter::interpreter::EthInterpreter,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    type Tracer = BinaryTracer<TestContext, EthInterpreter>;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn varint_roundtrip() {
+        let steps = [
+            BinaryStep::default(),
+            BinaryStep {
+                pc: 0x80,
+                opcode: 0xFF,
+                gas: u64::MAX,
+            },
+        ];
+        let mut out = Vec::new();
+        for step in &steps {
+            step.encode(&mut out).unwrap();
+        }
+        assert_eq!(read_binary_trace(&out[..]).unwrap(), steps);
+    }
+
+    #[test]
+    fn trace_roundtrip() {
+        let buffer = SharedBuffer::default();
+        let mut evm = evm_with_code(
+            &[PUSH1, 0x01, PUSH1, 0x02, ADD, STOP],
+            Tracer::new(Box::new(buffer.clone())),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let steps = read_binary_trace(&buffer.0.borrow()[..]).unwrap();
+        let gas = 100_000 - 21_000;
+        assert_eq!(
+            steps,
+            [
+                BinaryStep {
+                    pc: 0,
+                    opcode: PUSH1,
+                    gas
+                },
+                BinaryStep {
+                    pc: 2,
+                    opcode: PUSH1,
+                    gas: gas - 3
+                },
+                BinaryStep {
+                    pc: 4,
+                    opcode: ADD,
+                    gas: gas - 6
+                },
+                BinaryStep {
+                    pc: 5,
+                    opcode: STOP,
+                    gas: gas - 9
+                },
+            ]
+        );
+    }
+}
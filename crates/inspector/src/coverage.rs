@@ -0,0 +1,110 @@
+//! Inspector that records which instructions of the called contract were executed.
+use crate::Inspector;
+use revm::{
+    bytecode::bitvec::{bitvec, order::Lsb0, vec::BitVec},
+    interpreter::{
+        interpreter_types::{Jumps, LegacyBytecode, RuntimeFlag},
+        Interpreter, InterpreterTypes,
+    },
+};
+use std::vec::Vec;
+
+/// [Inspector] that tracks the executed program counters of the top level frame.
+///
+/// The coverage is reset at the start of every transaction. Nested calls and EOF
+/// bytecode are not tracked.
+#[derive(Clone, Debug)]
+pub struct CoverageTracer<CTX, INTR> {
+    /// Executed program counters, sized to the length of the original bytecode.
+    covered: BitVec<u8>,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> Default for CoverageTracer<CTX, INTR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CTX, INTR> CoverageTracer<CTX, INTR> {
+    /// Creates a new coverage tracer.
+    pub fn new() -> Self {
+        Self {
+            covered: BitVec::new(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the executed program counters in ascending order.
+    pub fn covered_pcs(&self) -> Vec<usize> {
+        self.covered.iter_ones().collect()
+    }
+
+    /// Returns the ratio of executed program counters to the bytecode length.
+    ///
+    /// Push immediates are never executed, so full coverage is only reached by
+    /// bytecode without them. The ratio is a float and only meant for reporting, gas
+    /// accounting never depends on it.
+    pub fn coverage_ratio(&self) -> f64 {
+        if self.covered.is_empty() {
+            return 0.0;
+        }
+        self.covered.count_ones() as f64 / self.covered.len() as f64
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for CoverageTracer<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn initialize_interp(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        if interp.depth() != 0 {
+            return;
+        }
+        let len = if interp.runtime_flag.is_eof() {
+            0
+        } else {
+            interp.bytecode.bytecode_len()
+        };
+        self.covered = bitvec![u8, Lsb0; 0; len];
+    }
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        if interp.depth() != 0 {
+            return;
+        }
+        let pc = interp.bytecode.pc();
+        if pc < self.covered.len() {
+            self.covered.set(pc, true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{JUMPDEST, JUMPI, PUSH1, STOP},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    type Tracer = CoverageTracer<TestContext, EthInterpreter>;
+
+    #[test]
+    fn untaken_branch_is_not_covered() {
+        // JUMPI with a zero condition falls through to `PUSH1 0x01, STOP`, the
+        // `JUMPDEST, PUSH1 0x02, STOP` branch at pc 8 is never executed.
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x08, JUMPI, PUSH1, 0x01, STOP, JUMPDEST, PUSH1, 0x02, STOP,
+        ];
+        let mut evm = evm_with_code(&code, Tracer::new());
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let tracer = &evm.context.inspector;
+        let covered = tracer.covered_pcs();
+        assert_eq!(covered, [0, 2, 4, 5, 7]);
+        assert!((8..code.len()).all(|pc| !covered.contains(&pc)));
+        assert_eq!(tracer.coverage_ratio(), 5.0 / 12.0);
+    }
+}
@@ -0,0 +1,109 @@
+//! Inspector that accumulates gas spent per opcode.
+use crate::Inspector;
+use core::cmp::Reverse;
+use revm::{
+    bytecode::opcode::OpCode,
+    interpreter::{
+        interpreter_types::{Jumps, LoopControl},
+        Interpreter, InterpreterTypes,
+    },
+};
+use std::vec::Vec;
+
+/// [Inspector] that sums gas spent by each opcode over all executed frames.
+///
+/// Gas of an instruction is the difference in remaining gas between
+/// [`step`][Inspector::step] and [`step_end`][Inspector::step_end], so calls and creates
+/// are accounted with the gas forwarded to the child frame.
+#[derive(Clone, Debug)]
+pub struct GasByOpcode<CTX, INTR> {
+    /// Opcode and remaining gas of the instruction being executed.
+    current: (u8, u64),
+    /// Total gas spent per opcode.
+    gas: [u64; 256],
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> Default for GasByOpcode<CTX, INTR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CTX, INTR> GasByOpcode<CTX, INTR> {
+    /// Creates a new empty accumulator.
+    pub fn new() -> Self {
+        Self {
+            current: (0, 0),
+            gas: [0; 256],
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns total gas spent by the given opcode.
+    pub fn gas_of(&self, opcode: u8) -> u64 {
+        self.gas[opcode as usize]
+    }
+
+    /// Returns up to `n` opcodes that spent the most gas, in descending order.
+    ///
+    /// Opcodes with equal gas are ordered by their byte value.
+    pub fn top_gas_consumers(&self, n: usize) -> Vec<(OpCode, u64)> {
+        let mut consumers: Vec<_> = (0..=u8::MAX)
+            .filter(|&op| self.gas[op as usize] != 0)
+            .filter_map(|op| OpCode::new(op).map(|opcode| (opcode, self.gas[op as usize])))
+            .collect();
+        consumers.sort_by_key(|&(_, gas)| Reverse(gas));
+        consumers.truncate(n);
+        consumers
+    }
+
+    /// Clears accumulated gas.
+    pub fn clear(&mut self) {
+        self.gas = [0; 256];
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for GasByOpcode<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        self.current = (interp.bytecode.opcode(), interp.control.gas().remaining());
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        let (opcode, gas_before) = self.current;
+        let spent = gas_before.saturating_sub(interp.control.gas().remaining());
+        self.gas[opcode as usize] = self.gas[opcode as usize].saturating_add(spent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{ADD, POP, PUSH1, SSTORE},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    type Accumulator = GasByOpcode<TestContext, EthInterpreter>;
+
+    #[test]
+    fn sstore_tops_gas_consumers() {
+        let code = vec![
+            PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x02, PUSH1, 0x01, SSTORE, PUSH1, 0x01, PUSH1,
+            0x02, ADD, POP,
+        ];
+        let mut evm = evm_with_code(&code, Accumulator::new());
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = &evm.context.inspector;
+        let top = inspector.top_gas_consumers(2);
+        assert_eq!(top[0].0, OpCode::SSTORE);
+        assert_eq!(top[1], (OpCode::PUSH1, 6 * 3));
+        assert_eq!(inspector.gas_of(ADD), 3);
+        assert_eq!(inspector.gas_of(POP), 2);
+    }
+}
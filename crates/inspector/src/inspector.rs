@@ -1,3 +1,4 @@
+use crate::OpcodeTrace;
 use auto_impl::auto_impl;
 use core::mem::MaybeUninit;
 use derive_where::derive_where;
@@ -6,7 +7,7 @@ use revm::{
     context::{block::BlockEnv, cfg::CfgEnv, tx::TxEnv, Cfg, JournaledState},
     context_interface::{
         block::BlockSetter,
-        journaled_state::{AccountLoad, Eip7702CodeLoad},
+        journaled_state::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint},
         result::EVMError,
         transaction::TransactionSetter,
         Block, BlockGetter, CfgGetter, DatabaseGetter, ErrorGetter, Journal, JournalStateGetter,
@@ -86,6 +87,24 @@ pub trait Inspector {
         let _ = context;
     }
 
+    /// Returns whether [`trace_step`][Inspector::trace_step] should be called.
+    ///
+    /// The [OpcodeTrace] is only constructed if this returns `true`.
+    #[inline]
+    fn wants_trace(&self) -> bool {
+        false
+    }
+
+    /// Called on each step of the interpreter with a snapshot of the instruction,
+    /// before [`step`][Inspector::step].
+    ///
+    /// Only called if [`wants_trace`][Inspector::wants_trace] returns `true`.
+    #[inline]
+    fn trace_step(&mut self, trace: &OpcodeTrace, context: &mut Self::Context) {
+        let _ = trace;
+        let _ = context;
+    }
+
     /// Called when a log is emitted.
     #[inline]
     fn log(
@@ -337,6 +356,18 @@ impl<
     ) -> Option<StateLoad<SelfDestructResult>> {
         self.inner.selfdestruct(address, target)
     }
+
+    fn checkpoint(&mut self) -> JournalCheckpoint {
+        self.inner.checkpoint()
+    }
+
+    fn checkpoint_commit(&mut self) {
+        self.inner.checkpoint_commit()
+    }
+
+    fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
+        self.inner.checkpoint_revert(checkpoint)
+    }
 }
 
 impl<INSP, BLOCK, TX, CFG, DB: Database, JOURNAL: Journal<Database = DB>, CHAIN> InspectorCtx
@@ -352,7 +383,11 @@ where
     type IT = EthInterpreter<()>;
 
     fn step(&mut self, interp: &mut Interpreter<Self::IT>) {
-        self.inspector.get_inspector().step(interp, &mut self.inner);
+        let inspector = self.inspector.get_inspector();
+        if inspector.wants_trace() {
+            inspector.trace_step(&OpcodeTrace::new(interp), &mut self.inner);
+        }
+        inspector.step(interp, &mut self.inner);
     }
 
     fn step_end(&mut self, interp: &mut Interpreter<Self::IT>) {
@@ -761,6 +796,8 @@ where
         mut frame_input: Self::FrameInit,
     ) -> Result<FrameOrResultGen<Self, Self::FrameResult>, Self::Error> {
         if let Some(output) = context.frame_start(&mut frame_input) {
+            // `return_result` frees the memory context of the skipped frame.
+            self.eth_frame.memory.borrow_mut().new_context();
             return Ok(FrameOrResultGen::Result(output));
         }
         let mut ret = self
@@ -845,3 +882,73 @@ pub type InspectorHandler<CTX, ERROR, PRECOMPILE> = EthHandler<
     EthPreExecution<CTX, ERROR>,
     InspectorEthExecution<CTX, ERROR, PRECOMPILE>,
 >;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{db_with_code, evm, TestContext};
+    use revm::{
+        bytecode::opcode::{CALL, GAS, PUSH1, PUSH2, STOP},
+        context_interface::result::{ExecutionResult, HaltReason},
+        interpreter::{Gas, InterpreterResult},
+        primitives::address,
+    };
+
+    const CALLER: Address = address!("0000000000000000000000000000000000000100");
+    const CALLEE: Address = address!("0000000000000000000000000000000000000200");
+
+    /// Returns more gas from calls to [CALLEE] than the caller has spent.
+    #[derive(Clone, Debug)]
+    struct GasInflator;
+
+    impl Inspector for GasInflator {
+        type Context = TestContext;
+        type InterpreterTypes = EthInterpreter;
+
+        fn call(&mut self, _: &mut Self::Context, inputs: &mut CallInputs) -> Option<CallOutcome> {
+            (inputs.target_address == CALLEE).then(|| {
+                CallOutcome::new(
+                    InterpreterResult {
+                        result: InstructionResult::Stop,
+                        output: Bytes::new(),
+                        gas: Gas::new(u64::MAX),
+                    },
+                    inputs.return_memory_offset.clone(),
+                )
+            })
+        }
+    }
+
+    fn transact(target: Address) -> ExecutionResult<HaltReason> {
+        // CALL to CALLEE forwarding all gas.
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH2, 0x02, 0x00,
+            GAS, CALL, STOP,
+        ];
+        let mut evm = evm(db_with_code(CALLER, &code), target, GasInflator);
+        evm.transact().unwrap().result
+    }
+
+    #[test]
+    fn returned_gas_underflow_halts() {
+        // Sub call returns more gas than the caller spent.
+        let result = transact(CALLER);
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::GasUnderflow,
+                gas_used: 100_000
+            }
+        ));
+
+        // Top level call returns more gas than the transaction gas limit.
+        let result = transact(CALLEE);
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::GasUnderflow,
+                gas_used: 100_000
+            }
+        ));
+    }
+}
@@ -5,18 +5,38 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+#[cfg(feature = "std")]
+mod binary;
+mod coverage;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
 mod gas;
+mod gas_by_opcode;
 mod inspector;
 mod noop;
+mod opcode_trace;
+mod precompile_mock;
+mod script;
+mod static_violation;
+mod storage_gas;
+#[cfg(test)]
+mod test_utils;
 
 pub use inspector::*;
+pub use opcode_trace::OpcodeTrace;
 
 /// [Inspector] implementations.
 pub mod inspectors {
+    #[cfg(feature = "std")]
+    pub use super::binary::{read_binary_trace, BinaryStep, BinaryTracer};
+    pub use super::coverage::CoverageTracer;
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
     pub use super::gas::GasInspector;
+    pub use super::gas_by_opcode::GasByOpcode;
     pub use super::noop::NoOpInspector;
+    pub use super::precompile_mock::{MockPrecompileFn, PrecompileMock};
+    pub use super::script::{ExecStep, ScriptRecorder};
+    pub use super::static_violation::StaticViolationDetector;
+    pub use super::storage_gas::StorageGasTracer;
 }
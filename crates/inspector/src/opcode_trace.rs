@@ -0,0 +1,94 @@
+//! Structured opcode trace passed to [`Inspector::trace_step`][crate::Inspector::trace_step].
+use revm::{
+    interpreter::{
+        interpreter_types::{Jumps, LoopControl, MemoryTrait},
+        Gas, Interpreter, InterpreterTypes, Stack,
+    },
+    primitives::U256,
+};
+
+/// Snapshot of the interpreter before an instruction is executed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpcodeTrace {
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Opcode of the instruction.
+    pub opcode: u8,
+    /// Gas before the instruction.
+    pub gas_before: Gas,
+    /// Top four stack items, top first. Missing items are zero.
+    pub stack_top: [U256; 4],
+    /// Memory size in bytes.
+    pub memory_size: usize,
+}
+
+impl OpcodeTrace {
+    /// Captures the trace of the instruction at the current instruction pointer.
+    pub fn new<INTR: InterpreterTypes<Stack = Stack>>(interp: &mut Interpreter<INTR>) -> Self {
+        let mut stack_top = [U256::ZERO; 4];
+        for (item, value) in stack_top.iter_mut().zip(interp.stack.data().iter().rev()) {
+            *item = *value;
+        }
+        Self {
+            pc: interp.bytecode.pc(),
+            opcode: interp.bytecode.opcode(),
+            gas_before: *interp.control.gas(),
+            stack_top,
+            memory_size: interp.memory.size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{evm_with_code, TestContext},
+        Inspector,
+    };
+    use revm::{
+        bytecode::opcode::{ADD, MSTORE, PUSH1, STOP},
+        interpreter::interpreter::EthInterpreter,
+    };
+    use std::vec::Vec;
+
+    #[derive(Clone, Debug, Default)]
+    struct Traces(Vec<OpcodeTrace>);
+
+    impl Inspector for Traces {
+        type Context = TestContext;
+        type InterpreterTypes = EthInterpreter;
+
+        fn wants_trace(&self) -> bool {
+            true
+        }
+
+        fn trace_step(&mut self, trace: &OpcodeTrace, _: &mut Self::Context) {
+            self.0.push(*trace);
+        }
+    }
+
+    #[test]
+    fn trace_step() {
+        let code = [PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x00, MSTORE, STOP];
+        let mut evm = evm_with_code(&code, Traces::default());
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let traces = &evm.context.inspector.0;
+        assert_eq!(traces.len(), 6);
+
+        let add = traces[2];
+        assert_eq!((add.pc, add.opcode), (4, ADD));
+        assert_eq!(add.gas_before.remaining(), 100_000 - 21_000 - 6);
+        assert_eq!(
+            add.stack_top,
+            [U256::from(2), U256::from(1), U256::ZERO, U256::ZERO]
+        );
+
+        let stop = traces[5];
+        assert_eq!((stop.pc, stop.opcode), (8, STOP));
+        assert_eq!(stop.memory_size, 32);
+        assert_eq!(stop.stack_top, [U256::ZERO; 4]);
+    }
+}
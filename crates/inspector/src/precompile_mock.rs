@@ -0,0 +1,129 @@
+//! Inspector that replaces precompile results with mocked ones.
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, Gas, InstructionResult, InterpreterResult, InterpreterTypes,
+    },
+    precompile::{PrecompileErrors, PrecompileResult},
+    primitives::{Address, Bytes, HashMap},
+};
+
+/// Mocked precompile function.
+pub type MockPrecompileFn = fn(&[u8]) -> PrecompileResult;
+
+/// [Inspector] that returns mocked results for calls to the registered addresses.
+///
+/// Calls to mocked addresses don't execute the real precompile or code at the address,
+/// the mock output and gas are returned to the caller instead.
+#[derive(Clone, Debug)]
+pub struct PrecompileMock<CTX, INTR> {
+    mocks: HashMap<Address, MockPrecompileFn>,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> Default for PrecompileMock<CTX, INTR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CTX, INTR> PrecompileMock<CTX, INTR> {
+    /// Creates a new inspector without mocks.
+    pub fn new() -> Self {
+        Self {
+            mocks: HashMap::default(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Mocks calls to `address` with the given function.
+    pub fn mock_precompile(&mut self, address: Address, mock: MockPrecompileFn) -> &mut Self {
+        self.mocks.insert(address, mock);
+        self
+    }
+
+    /// Removes the mock of `address`.
+    pub fn remove_mock(&mut self, address: &Address) -> Option<MockPrecompileFn> {
+        self.mocks.remove(address)
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for PrecompileMock<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn call(&mut self, _: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let mock = self.mocks.get(&inputs.bytecode_address)?;
+
+        let mut result = InterpreterResult {
+            result: InstructionResult::Return,
+            gas: Gas::new(inputs.gas_limit),
+            output: Bytes::new(),
+        };
+        match mock(&inputs.input[..]) {
+            Ok(output) if !result.gas.record_cost(output.gas_used) => {
+                result.result = InstructionResult::PrecompileOOG;
+            }
+            Ok(output) => result.output = output.bytes,
+            Err(PrecompileErrors::Error(e)) => {
+                result.result = if e.is_oog() {
+                    InstructionResult::PrecompileOOG
+                } else {
+                    InstructionResult::PrecompileError
+                };
+            }
+            Err(PrecompileErrors::Fatal { .. }) => {
+                result.result = InstructionResult::FatalExternalError;
+            }
+        }
+        Some(CallOutcome::new(
+            result,
+            inputs.return_memory_offset.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{CALL, GAS, PUSH1, RETURN},
+        interpreter::interpreter::EthInterpreter,
+        precompile::{u64_to_address, PrecompileOutput},
+        primitives::address,
+    };
+
+    type Mock = PrecompileMock<TestContext, EthInterpreter>;
+
+    const SIGNER: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn mock_ecrecover(_: &[u8]) -> PrecompileResult {
+        Ok(PrecompileOutput::new(
+            3000,
+            SIGNER.into_word().to_vec().into(),
+        ))
+    }
+
+    /// Calls ecrecover with 128 zero bytes and returns its 32 byte output.
+    fn call_ecrecover(mock: Mock) -> Bytes {
+        let code = [
+            PUSH1, 0x20, PUSH1, 0x00, PUSH1, 0x80, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01, GAS,
+            CALL, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let mut evm = evm_with_code(&code, mock);
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        result.output().unwrap().clone()
+    }
+
+    #[test]
+    fn mocked_ecrecover() {
+        // Real ecrecover fails to recover a signer from zeroed input.
+        assert_eq!(call_ecrecover(Mock::new()), Bytes::from([0u8; 32]));
+
+        let mut mock = Mock::new();
+        mock.mock_precompile(u64_to_address(1), mock_ecrecover);
+        assert_eq!(call_ecrecover(mock), Bytes::from(SIGNER.into_word().0));
+    }
+}
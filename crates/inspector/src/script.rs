@@ -0,0 +1,107 @@
+//! Inspector that records an execution as a replayable list of steps.
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        interpreter_types::{Jumps, LoopControl},
+        Interpreter, InterpreterTypes, Stack,
+    },
+    primitives::U256,
+};
+use std::vec::Vec;
+
+/// Single executed instruction recorded by [ScriptRecorder].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecStep {
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Executed opcode.
+    pub opcode: u8,
+    /// Stack before the instruction, bottom first.
+    pub stack_before: Vec<U256>,
+    /// Remaining gas before the instruction.
+    pub gas_before: u64,
+}
+
+/// [Inspector] that records every executed instruction as an [ExecStep].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScriptRecorder<CTX, INTR> {
+    steps: Vec<ExecStep>,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> ScriptRecorder<CTX, INTR> {
+    /// Creates a new recorder.
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns recorded steps.
+    pub fn steps(&self) -> &[ExecStep] {
+        &self.steps
+    }
+
+    /// Consumes the recorder and returns recorded steps.
+    pub fn into_steps(self) -> Vec<ExecStep> {
+        self.steps
+    }
+
+    /// Clears recorded steps.
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes<Stack = Stack>> Inspector for ScriptRecorder<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        self.steps.push(ExecStep {
+            pc: interp.bytecode.pc(),
+            opcode: interp.bytecode.opcode(),
+            stack_before: interp.stack.data().to_vec(),
+            gas_before: interp.control.gas().remaining(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{ADD, PUSH1, STOP},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    type Recorder = ScriptRecorder<TestContext, EthInterpreter>;
+
+    fn run(code: Vec<u8>) -> Vec<ExecStep> {
+        let mut evm = evm_with_code(&code, Recorder::new());
+        assert!(evm.transact().unwrap().result.is_success());
+        evm.context.inspector.into_steps()
+    }
+
+    #[test]
+    fn records_steps() {
+        let steps = run(vec![PUSH1, 0x01, PUSH1, 0x02, ADD, STOP]);
+        let opcodes: Vec<_> = steps.iter().map(|s| (s.pc, s.opcode)).collect();
+        assert_eq!(opcodes, [(0, PUSH1), (2, PUSH1), (4, ADD), (5, STOP)]);
+        assert_eq!(steps[2].stack_before, [U256::from(1), U256::from(2)]);
+        assert_eq!(steps[3].stack_before, [U256::from(3)]);
+        assert_eq!(steps[0].gas_before - steps[1].gas_before, 3);
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn serde_roundtrip() {
+        let steps = run(vec![PUSH1, 0x01, PUSH1, 0x02, ADD, STOP]);
+        let json = serde_json::to_string(&steps).unwrap();
+        let decoded: Vec<ExecStep> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, steps);
+    }
+}
@@ -0,0 +1,115 @@
+//! Inspector that detects state changes in a supposedly read-only execution.
+use crate::Inspector;
+use revm::{
+    bytecode::opcode::{self, OpCode},
+    interpreter::{
+        interpreter_types::Jumps, CallInputs, CallOutcome, Interpreter, InterpreterTypes,
+    },
+};
+
+/// [Inspector] that records the first state-changing opcode executed.
+///
+/// State-changing opcodes are the ones disallowed in a static context: `SSTORE`, `TSTORE`,
+/// `LOG0`-`LOG4`, `CREATE`, `CREATE2`, `EOFCREATE`, `SELFDESTRUCT` and `CALL`/`EXTCALL`
+/// transferring value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StaticViolationDetector<CTX, INTR> {
+    /// Program counter and opcode of the last executed instruction.
+    last: Option<(usize, u8)>,
+    /// First state-changing instruction.
+    violation: Option<(usize, OpCode)>,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> StaticViolationDetector<CTX, INTR> {
+    /// Creates a new detector.
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            violation: None,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns program counter and opcode of the first state-changing instruction, if any.
+    pub fn violated(&self) -> Option<(usize, OpCode)> {
+        self.violation
+    }
+
+    /// Clears the recorded violation.
+    pub fn clear(&mut self) {
+        self.last = None;
+        self.violation = None;
+    }
+
+    fn record(&mut self, pc: usize, opcode: u8) {
+        if self.violation.is_none() {
+            self.violation = OpCode::new(opcode).map(|op| (pc, op));
+        }
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for StaticViolationDetector<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        let pc = interp.bytecode.pc();
+        let opcode = interp.bytecode.opcode();
+        self.last = Some((pc, opcode));
+        if matches!(
+            opcode,
+            opcode::SSTORE | opcode::TSTORE | opcode::LOG0
+                ..=opcode::LOG4
+                    | opcode::CREATE
+                    | opcode::CREATE2
+                    | opcode::EOFCREATE
+                    | opcode::SELFDESTRUCT
+        ) {
+            self.record(pc, opcode);
+        }
+    }
+
+    fn call(&mut self, _: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        // Value is only known once the call inputs are created.
+        if let Some((pc, opcode @ (opcode::CALL | opcode::EXTCALL))) = self.last {
+            if inputs.transfers_value() {
+                self.record(pc, opcode);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{ADD, MSTORE, PUSH1, RETURN, SSTORE},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    type Detector = StaticViolationDetector<TestContext, EthInterpreter>;
+
+    fn run(code: Vec<u8>) -> Option<(usize, OpCode)> {
+        let mut evm = evm_with_code(&code, Detector::new());
+        assert!(evm.transact().unwrap().result.is_success());
+        evm.context.inspector.violated()
+    }
+
+    #[test]
+    fn view_has_no_violation() {
+        // Returns `1 + 2`.
+        let code = vec![
+            PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        assert_eq!(run(code), None);
+    }
+
+    #[test]
+    fn sstore_is_violation() {
+        let code = vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE];
+        assert_eq!(run(code), Some((4, OpCode::SSTORE)));
+    }
+}
@@ -0,0 +1,106 @@
+//! Inspector that accumulates gas spent on storage access.
+use crate::Inspector;
+use revm::{
+    bytecode::opcode,
+    interpreter::{
+        interpreter_types::{Jumps, LoopControl},
+        Interpreter, InterpreterTypes,
+    },
+};
+
+/// [Inspector] that sums gas spent by storage opcodes over all executed frames.
+///
+/// Persistent (`SLOAD`, `SSTORE`) and transient (`TLOAD`, `TSTORE`) storage are
+/// accounted separately. Gas of an instruction is the difference in remaining gas
+/// between [`step`][Inspector::step] and [`step_end`][Inspector::step_end].
+#[derive(Clone, Debug)]
+pub struct StorageGasTracer<CTX, INTR> {
+    /// Opcode and remaining gas of the instruction being executed.
+    current: (u8, u64),
+    /// Gas spent by `SLOAD` and `SSTORE`.
+    storage: u64,
+    /// Gas spent by `TLOAD` and `TSTORE`.
+    transient_storage: u64,
+    _phantom: core::marker::PhantomData<(CTX, INTR)>,
+}
+
+impl<CTX, INTR> Default for StorageGasTracer<CTX, INTR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CTX, INTR> StorageGasTracer<CTX, INTR> {
+    /// Creates a new empty tracer.
+    pub fn new() -> Self {
+        Self {
+            current: (0, 0),
+            storage: 0,
+            transient_storage: 0,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns total gas spent by `SLOAD` and `SSTORE`.
+    pub fn storage_gas(&self) -> u64 {
+        self.storage
+    }
+
+    /// Returns total gas spent by `TLOAD` and `TSTORE`.
+    pub fn transient_storage_gas(&self) -> u64 {
+        self.transient_storage
+    }
+
+    /// Clears accumulated gas.
+    pub fn clear(&mut self) {
+        self.storage = 0;
+        self.transient_storage = 0;
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector for StorageGasTracer<CTX, INTR> {
+    type Context = CTX;
+    type InterpreterTypes = INTR;
+
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        self.current = (interp.bytecode.opcode(), interp.control.gas().remaining());
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, _: &mut CTX) {
+        let (opcode, gas_before) = self.current;
+        let total = match opcode {
+            opcode::SLOAD | opcode::SSTORE => &mut self.storage,
+            opcode::TLOAD | opcode::TSTORE => &mut self.transient_storage,
+            _ => return,
+        };
+        let spent = gas_before.saturating_sub(interp.control.gas().remaining());
+        *total = total.saturating_add(spent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evm_with_code, TestContext};
+    use revm::{
+        bytecode::opcode::{PUSH1, SSTORE, TSTORE},
+        interpreter::interpreter::EthInterpreter,
+    };
+
+    type Tracer = StorageGasTracer<TestContext, EthInterpreter>;
+
+    #[test]
+    fn storage_gas() {
+        let code = [
+            PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x02, PUSH1, 0x01, SSTORE, PUSH1, 0x01, PUSH1,
+            0x00, TSTORE,
+        ];
+        let mut evm = evm_with_code(&code, Tracer::new());
+        assert!(evm.transact().unwrap().result.is_success());
+
+        // Two cold `SSTORE`s setting zero slots to non-zero values.
+        let tracer = &evm.context.inspector;
+        assert_eq!(tracer.storage_gas(), 2 * (2_100 + 20_000));
+        assert_eq!(tracer.transient_storage_gas(), 100);
+    }
+}
@@ -0,0 +1,53 @@
+//! Fixtures shared by the inspector tests.
+
+use crate::{inspector_handler, Inspector, InspectorContext, InspectorMainEvm};
+use database::InMemoryDB;
+use revm::{
+    bytecode::Bytecode,
+    context::{BlockEnv, CfgEnv, TxEnv},
+    primitives::{address, Address, TxKind},
+    state::AccountInfo,
+    Context,
+};
+
+/// Context the test inspectors are written against.
+pub(crate) type TestContext = Context<BlockEnv, TxEnv, CfgEnv, InMemoryDB>;
+
+/// Account the test transactions call.
+pub(crate) const TARGET: Address = address!("0000000000000000000000000000000000000100");
+
+/// Database with legacy `code` deployed at `address`.
+pub(crate) fn db_with_code(address: Address, code: &[u8]) -> InMemoryDB {
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        address,
+        AccountInfo::from_bytecode(Bytecode::new_legacy(code.to_vec().into())),
+    );
+    db
+}
+
+/// Inspected EVM over `db` with a 100_000 gas transaction calling `target`.
+pub(crate) fn evm<INSP: Inspector>(
+    db: InMemoryDB,
+    target: Address,
+    inspector: INSP,
+) -> InspectorMainEvm<InMemoryDB, INSP> {
+    let mut evm = InspectorMainEvm::new(
+        InspectorContext::new(Context::builder().with_db(db), inspector),
+        inspector_handler(),
+    );
+    evm.context.inner.modify_tx(|tx| {
+        tx.caller = Address::ZERO;
+        tx.transact_to = TxKind::Call(target);
+        tx.gas_limit = 100_000;
+    });
+    evm
+}
+
+/// Inspected EVM calling legacy `code` deployed at [TARGET].
+pub(crate) fn evm_with_code<INSP: Inspector>(
+    code: &[u8],
+    inspector: INSP,
+) -> InspectorMainEvm<InMemoryDB, INSP> {
+    evm(db_with_code(TARGET, code), TARGET, inspector)
+}
@@ -0,0 +1,87 @@
+use bytecode::{
+    opcode::{ADD, PUSH1, STOP},
+    Bytecode,
+};
+use context::{BlockEnv, CfgEnv, TxEnv};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use primitives::{Address, Bytes, U256};
+use revm_interpreter::{
+    interpreter::EthInterpreter, table::make_instruction_table, DummyHost, InputsImpl, Interpreter,
+    SharedMemory,
+};
+use specification::hardfork::SpecId;
+use std::{cell::RefCell, rc::Rc};
+
+type TestHost = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+const CALLS: usize = 1000;
+
+fn inputs() -> InputsImpl {
+    InputsImpl {
+        target_address: Address::ZERO,
+        caller_address: Address::ZERO,
+        input: Bytes::default(),
+        call_value: U256::ZERO,
+    }
+}
+
+/// Compares creating a new interpreter for every call with resetting a single one.
+pub fn benchmark_reset(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Interpreter reuse benchmarks");
+    let group_name = |description: &str| format!("{CALLS} calls | {description}");
+
+    let table = make_instruction_table::<EthInterpreter, TestHost>();
+    let bytecode = Bytecode::new_raw([PUSH1, 0x01, PUSH1, 0x02, ADD, STOP].into());
+    let memory = Rc::new(RefCell::new(SharedMemory::new()));
+
+    group.bench_function(group_name("new"), |b| {
+        let mut host = TestHost::default();
+        b.iter(|| {
+            for _ in 0..CALLS {
+                let mut interp = Interpreter::<EthInterpreter>::new(
+                    memory.clone(),
+                    bytecode.clone(),
+                    inputs(),
+                    false,
+                    false,
+                    SpecId::LATEST,
+                    100_000,
+                );
+                black_box(interp.run(&table, &mut host));
+            }
+        })
+    });
+
+    group.bench_function(group_name("reset"), |b| {
+        let mut host = TestHost::default();
+        let mut interp = Interpreter::<EthInterpreter>::new(
+            memory.clone(),
+            bytecode.clone(),
+            inputs(),
+            false,
+            false,
+            SpecId::LATEST,
+            100_000,
+        );
+        b.iter(|| {
+            for _ in 0..CALLS {
+                interp.reset(
+                    bytecode.clone(),
+                    inputs(),
+                    false,
+                    false,
+                    SpecId::LATEST,
+                    100_000,
+                );
+                black_box(interp.run(&table, &mut host));
+            }
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = benchmark_reset
+}
+criterion_main!(benches);
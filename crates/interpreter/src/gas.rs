@@ -6,6 +6,10 @@ mod constants;
 pub use calc::*;
 pub use constants::*;
 
+use crate::InstructionResult;
+use core::num::NonZeroU64;
+use specification::hardfork::SpecId;
+
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -43,6 +47,15 @@ impl Gas {
         }
     }
 
+    /// Sets the denominator of the quadratic part of the memory expansion cost.
+    ///
+    /// Defaults to [`MEMORY_QUAD_COEFF_DIV`], chains with custom memory pricing can override it
+    /// before execution starts.
+    #[inline]
+    pub fn set_memory_quad_coeff_div(&mut self, quad_coeff_div: NonZeroU64) {
+        self.memory.quad_coeff_div = quad_coeff_div;
+    }
+
     /// Returns the gas limit.
     #[inline]
     pub const fn limit(&self) -> u64 {
@@ -70,6 +83,17 @@ impl Gas {
         self.limit - self.remaining
     }
 
+    /// Returns a snapshot of the limit, spent, memory expansion and refunded gas.
+    #[inline]
+    pub const fn report(&self) -> GasReport {
+        GasReport {
+            limit: self.limit,
+            spent: self.spent(),
+            memory: self.memory.expansion_cost,
+            refunded: self.refunded,
+        }
+    }
+
     /// Returns the amount of gas remaining.
     #[inline]
     pub const fn remaining(&self) -> u64 {
@@ -82,9 +106,63 @@ impl Gas {
     }
 
     /// Erases a gas cost from the totals.
+    ///
+    /// Returns `false` and leaves the gas unchanged if more gas is returned than was spent.
     #[inline]
-    pub fn erase_cost(&mut self, returned: u64) {
+    pub fn erase_cost(&mut self, returned: u64) -> bool {
+        if returned > self.spent() {
+            return false;
+        }
         self.remaining += returned;
+        true
+    }
+
+    /// Raises the limit and the remaining gas by `extra`.
+    ///
+    /// Used to run an instruction whose charge is replaced afterwards, the extra gas is
+    /// taken back with [`Gas::remove_headroom`].
+    #[inline]
+    pub(crate) fn add_headroom(&mut self, extra: u64) {
+        self.limit += extra;
+        self.remaining += extra;
+    }
+
+    /// Lowers the limit and the remaining gas by `extra` added with [`Gas::add_headroom`].
+    ///
+    /// Returns `false` and leaves no gas remaining if more than the headroom was spent.
+    #[inline]
+    pub(crate) fn remove_headroom(&mut self, extra: u64) -> bool {
+        self.limit -= extra;
+        match self.remaining.checked_sub(extra) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                true
+            }
+            None => {
+                self.remaining = 0;
+                false
+            }
+        }
+    }
+
+    /// Saves the remaining gas, refund and memory expansion state.
+    #[inline]
+    pub const fn checkpoint(&self) -> GasCheckpoint {
+        GasCheckpoint {
+            remaining: self.remaining,
+            refunded: self.refunded,
+            memory: self.memory,
+        }
+    }
+
+    /// Restores the state saved by [`Gas::checkpoint`].
+    ///
+    /// The gas limit is not changed.
+    #[inline]
+    pub fn revert(&mut self, checkpoint: GasCheckpoint) {
+        self.remaining = checkpoint.remaining;
+        self.refunded = checkpoint.refunded;
+        self.memory = checkpoint.memory;
     }
 
     /// Spends all remaining gas.
@@ -109,8 +187,29 @@ impl Gas {
     /// Related to EIP-3529: Reduction in refunds
     #[inline]
     pub fn set_final_refund(&mut self, is_london: bool) {
-        let max_refund_quotient = if is_london { 5 } else { 2 };
-        self.refunded = (self.refunded() as u64).min(self.spent() / max_refund_quotient) as i64;
+        self.refunded = self.capped_refund(is_london) as i64;
+    }
+
+    /// Returns the refund that is settled at the end of the transaction.
+    ///
+    /// Negative refunds are clamped to zero and the result is capped to a fraction of the
+    /// spent gas: half before London and a fifth after it (EIP-3529).
+    #[inline]
+    pub fn settled_refund(&self, spec: SpecId) -> u64 {
+        self.capped_refund(spec.is_enabled_in(SpecId::LONDON))
+    }
+
+    /// Returns the refund capped to a fifth of the spent gas (EIP-3529).
+    ///
+    /// Before London the cap is half of the spent gas. Negative refunds are clamped to zero.
+    #[inline]
+    pub fn effective_refund(&self, spec: SpecId) -> u64 {
+        self.capped_refund(spec.is_enabled_in(SpecId::LONDON))
+    }
+
+    #[inline]
+    fn capped_refund(&self, is_london: bool) -> u64 {
+        capped_refund(self.refunded, self.spent(), is_london)
     }
 
     /// Set a refund value. This overrides the current refund value.
@@ -147,6 +246,113 @@ impl Gas {
 
         MemoryExtensionResult::Extended
     }
+
+    /// Returns the memory expansion cost of `num_words` words.
+    #[inline]
+    pub const fn memory_expansion_cost(&self, num_words: usize) -> u64 {
+        memory_gas_with_quad_coeff_div(num_words, self.memory.quad_coeff_div.get())
+    }
+
+    /// Raises the memory expansion cost to `new_memory_cost`, spending the difference.
+    ///
+    /// Returns [`InstructionResult::OutOfGas`] if the difference exceeds the remaining gas and
+    /// [`InstructionResult::Continue`] otherwise. A lower cost leaves the gas unchanged.
+    #[inline]
+    #[must_use = "internally uses record_cost that flags out of gas error"]
+    pub fn record_memory_gas(&mut self, new_memory_cost: u64) -> InstructionResult {
+        match self.memory.record_new_cost(new_memory_cost) {
+            Some(additional_cost) if !self.record_cost(additional_cost) => {
+                InstructionResult::OutOfGas
+            }
+            _ => InstructionResult::Continue,
+        }
+    }
+}
+
+/// Remaining gas without the limit, refund and memory breakdown of [`Gas`].
+///
+/// Converting [`Gas`] into it is lossy, [`RemainingGas::into_gas`] only restores an
+/// approximation of the original.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemainingGas(pub u64);
+
+impl From<Gas> for RemainingGas {
+    #[inline]
+    fn from(gas: Gas) -> Self {
+        Self(gas.remaining)
+    }
+}
+
+impl RemainingGas {
+    /// Reconstructs [`Gas`] with the given `limit`.
+    ///
+    /// Refund and memory expansion state are lost in the conversion to [`RemainingGas`],
+    /// so they are reset. Remaining gas is capped at `limit`.
+    #[inline]
+    pub const fn into_gas(self, limit: u64) -> Gas {
+        let mut gas = Gas::new(limit);
+        if self.0 < limit {
+            gas.remaining = self.0;
+        }
+        gas
+    }
+}
+
+/// Gas state saved by [`Gas::checkpoint`] and restored by [`Gas::revert`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GasCheckpoint {
+    remaining: u64,
+    refunded: i64,
+    memory: MemoryGas,
+}
+
+/// Snapshot of the gas accounting of [`Gas`], returned by [`Gas::report`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasReport {
+    /// The gas limit.
+    pub limit: u64,
+    /// Gas spent, including memory expansion.
+    pub spent: u64,
+    /// Current memory expansion cost, included in `spent`.
+    pub memory: u64,
+    /// Refunded gas before the EIP-3529 cap is applied.
+    pub refunded: i64,
+}
+
+impl GasReport {
+    /// Returns the refund that is settled at the end of the transaction.
+    ///
+    /// Same cap as [`Gas::settled_refund`]: half of the spent gas before London and a fifth
+    /// after it (EIP-3529). Negative refunds are clamped to zero.
+    #[inline]
+    pub const fn effective_refund(&self, spec: SpecId) -> u64 {
+        capped_refund(
+            self.refunded,
+            self.spent,
+            spec.is_enabled_in(SpecId::LONDON),
+        )
+    }
+
+    /// Returns the spent gas minus the [effective refund][GasReport::effective_refund].
+    #[inline]
+    pub const fn net_cost(&self, spec: SpecId) -> u64 {
+        self.spent - self.effective_refund(spec)
+    }
+}
+
+/// Clamps `refunded` to zero and caps it to a fraction of `spent`.
+#[inline]
+const fn capped_refund(refunded: i64, spent: u64, is_london: bool) -> u64 {
+    let max_refund_quotient = if is_london { 5 } else { 2 };
+    let refunded = if refunded < 0 { 0 } else { refunded as u64 };
+    let cap = spent / max_refund_quotient;
+    if refunded < cap {
+        refunded
+    } else {
+        cap
+    }
 }
 
 pub enum MemoryExtensionResult {
@@ -162,13 +368,31 @@ pub enum MemoryExtensionResult {
 /// It contains the current memory length and its memory expansion cost.
 ///
 /// It allows us to split gas accounting from memory structure.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryGas {
     /// Current memory length
     pub words_num: usize,
     /// Current memory expansion cost
     pub expansion_cost: u64,
+    /// Denominator of the quadratic part of the memory expansion cost
+    #[cfg_attr(feature = "serde", serde(default = "default_quad_coeff_div"))]
+    pub quad_coeff_div: NonZeroU64,
+}
+
+/// [`MEMORY_QUAD_COEFF_DIV`] as a [`NonZeroU64`].
+const DEFAULT_QUAD_COEFF_DIV: NonZeroU64 = NonZeroU64::new(MEMORY_QUAD_COEFF_DIV).unwrap();
+
+#[cfg(feature = "serde")]
+const fn default_quad_coeff_div() -> NonZeroU64 {
+    DEFAULT_QUAD_COEFF_DIV
+}
+
+impl Default for MemoryGas {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MemoryGas {
@@ -176,6 +400,7 @@ impl MemoryGas {
         Self {
             words_num: 0,
             expansion_cost: 0,
+            quad_coeff_div: DEFAULT_QUAD_COEFF_DIV,
         }
     }
 
@@ -185,10 +410,209 @@ impl MemoryGas {
             return None;
         }
         self.words_num = new_num;
-        let mut cost = crate::gas::calc::memory_gas(new_num);
-        core::mem::swap(&mut self.expansion_cost, &mut cost);
-        // Safe to subtract because we know that new_len > length
-        // Notice the swap above.
-        Some(self.expansion_cost - cost)
+        self.record_new_cost(memory_gas_with_quad_coeff_div(
+            new_num,
+            self.quad_coeff_div.get(),
+        ))
+    }
+
+    /// Raises the expansion cost to `new_cost` and returns the increase, or `None` if the
+    /// cost does not grow.
+    #[inline]
+    pub fn record_new_cost(&mut self, new_cost: u64) -> Option<u64> {
+        if new_cost <= self.expansion_cost {
+            return None;
+        }
+        let additional_cost = new_cost - self.expansion_cost;
+        self.expansion_cost = new_cost;
+        Some(additional_cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_expansion_cost_integer() {
+        // Reference formula in wider integers, without saturation.
+        let reference = |words: u128| 3 * words + words * words / 512;
+
+        let mut memory = MemoryGas::new();
+        let mut total = 0;
+        for words in [1, 2, 31, 32, 33, 512, 1_000, 1 << 16, 1 << 20] {
+            assert_eq!(
+                memory_gas(words) as u128,
+                reference(words as u128),
+                "{words}"
+            );
+            // Incremental expansion costs add up to the cost of the final size.
+            total += memory.record_new_len(words).unwrap();
+            assert_eq!(total, memory_gas(words));
+            assert_eq!(memory.expansion_cost, total);
+        }
+    }
+
+    #[test]
+    fn record_memory_gas() {
+        let mut gas = Gas::new(100);
+        assert_eq!(gas.record_memory_gas(30), InstructionResult::Continue);
+        assert_eq!(gas.record_memory_gas(20), InstructionResult::Continue);
+        assert_eq!(gas.spent(), 30);
+
+        // Only the difference is spent.
+        assert_eq!(gas.record_memory_gas(50), InstructionResult::Continue);
+        assert_eq!(gas.spent(), 50);
+
+        assert_eq!(gas.record_memory_gas(101), InstructionResult::OutOfGas);
+        assert_eq!(gas.spent(), 50);
+    }
+
+    #[test]
+    fn settled_refund() {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(50_000));
+
+        // Net negative refund settles to zero.
+        gas.record_refund(4_800);
+        gas.record_refund(-19_900);
+        assert_eq!(gas.settled_refund(SpecId::CANCUN), 0);
+        assert_eq!(gas.settled_refund(SpecId::BERLIN), 0);
+
+        // Under the cap.
+        gas.set_refund(4_800);
+        assert_eq!(gas.settled_refund(SpecId::CANCUN), 4_800);
+        assert_eq!(gas.settled_refund(SpecId::BERLIN), 4_800);
+
+        // Over the cap: a fifth of spent gas after London, half before it.
+        gas.set_refund(30_000);
+        assert_eq!(gas.settled_refund(SpecId::CANCUN), 10_000);
+        assert_eq!(gas.settled_refund(SpecId::BERLIN), 25_000);
+
+        gas.set_final_refund(true);
+        assert_eq!(gas.refunded(), 10_000);
+    }
+
+    #[test]
+    fn effective_refund() {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(50_000));
+        gas.record_refund(-100);
+        assert_eq!(gas.effective_refund(SpecId::LONDON), 0);
+
+        gas.set_refund(9_999);
+        assert_eq!(gas.effective_refund(SpecId::LONDON), 9_999);
+
+        // EIP-3529: capped to `spent / 5`, `spent / 2` before London.
+        gas.set_refund(20_000);
+        assert_eq!(gas.effective_refund(SpecId::LONDON), 10_000);
+        assert_eq!(gas.effective_refund(SpecId::BERLIN), 20_000);
+        gas.set_refund(30_000);
+        assert_eq!(gas.effective_refund(SpecId::BERLIN), 25_000);
+    }
+
+    #[test]
+    fn report() {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(50_000));
+        assert!(matches!(
+            gas.record_memory_expansion(1024),
+            MemoryExtensionResult::Extended
+        ));
+        gas.record_refund(4_800);
+
+        let report = gas.report();
+        assert_eq!(report.limit, 100_000);
+        assert_eq!(report.spent, gas.spent());
+        assert_eq!(report.memory, memory_gas(1024));
+        assert_eq!(report.effective_refund(SpecId::CANCUN), 4_800);
+        assert_eq!(report.net_cost(SpecId::CANCUN), gas.spent() - 4_800);
+
+        gas.set_refund(30_000);
+        let report = gas.report();
+        assert_eq!(report.effective_refund(SpecId::CANCUN), gas.spent() / 5);
+        assert_eq!(report.effective_refund(SpecId::BERLIN), gas.spent() / 2);
+        assert_eq!(
+            report.effective_refund(SpecId::CANCUN),
+            gas.settled_refund(SpecId::CANCUN)
+        );
+        assert_eq!(
+            report.net_cost(SpecId::CANCUN),
+            gas.spent() - gas.spent() / 5
+        );
+
+        gas.set_refund(-1);
+        assert_eq!(gas.report().effective_refund(SpecId::CANCUN), 0);
+    }
+
+    #[test]
+    fn checkpoint_revert() {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(1_000));
+        gas.record_refund(100);
+        let checkpoint = gas.checkpoint();
+        let saved = gas;
+
+        assert!(gas.record_cost(20_000));
+        gas.record_refund(4_800);
+        assert!(matches!(
+            gas.record_memory_expansion(32),
+            MemoryExtensionResult::Extended
+        ));
+        assert_ne!(gas, saved);
+
+        gas.revert(checkpoint);
+        assert_eq!(gas, saved);
+    }
+
+    #[test]
+    fn erase_cost_underflow() {
+        let mut gas = Gas::new(100);
+        assert!(gas.record_cost(30));
+        assert!(!gas.erase_cost(31));
+        assert_eq!(gas.remaining(), 70);
+        assert!(gas.erase_cost(30));
+        assert_eq!(gas.spent(), 0);
+    }
+
+    #[test]
+    fn memory_expansion_custom_quad_coeff_div() {
+        let words = 1024;
+
+        let mut default = Gas::new(u64::MAX);
+        let mut custom = Gas::new(u64::MAX);
+        custom.set_memory_quad_coeff_div(NonZeroU64::new(MEMORY_QUAD_COEFF_DIV / 2).unwrap());
+
+        assert!(matches!(
+            default.record_memory_expansion(words),
+            MemoryExtensionResult::Extended
+        ));
+        assert!(matches!(
+            custom.record_memory_expansion(words),
+            MemoryExtensionResult::Extended
+        ));
+
+        assert_eq!(default.spent(), memory_gas(words));
+        assert_eq!(default.spent(), 3 * 1024 + 1024 * 1024 / 512);
+        assert_eq!(custom.spent(), 3 * 1024 + 1024 * 1024 / 256);
+        assert!(custom.spent() > default.spent());
+    }
+
+    #[test]
+    fn remaining_gas() {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(30_000));
+        gas.record_refund(4_800);
+
+        let remaining = RemainingGas::from(gas);
+        assert_eq!(remaining.0, gas.remaining());
+
+        let restored = remaining.into_gas(gas.limit());
+        assert_eq!(restored.remaining(), gas.remaining());
+        assert_eq!(restored.spent(), gas.spent());
+        // Refund is not preserved.
+        assert_eq!(restored.refunded(), 0);
+
+        assert_eq!(RemainingGas(200).into_gas(100).remaining(), 100);
     }
 }
@@ -1,11 +1,13 @@
 use super::constants::*;
-use crate::{num_words, tri, SStoreResult, SelfDestructResult, StateLoad};
+use crate::{num_words, tri, CallGasBreakdown, SStoreResult, SelfDestructResult, StateLoad};
+use bytecode::opcode::OpCode;
 use context_interface::{
     journaled_state::{AccountLoad, Eip7702CodeLoad},
     transaction::AccessListTrait,
 };
 use primitives::U256;
 use specification::{eip7702, hardfork::SpecId};
+use std::vec::Vec;
 
 /// `SSTORE` opcode refund calculation.
 #[allow(clippy::collapsible_else_if)]
@@ -141,6 +143,15 @@ pub const fn log_cost(n: u8, len: u64) -> Option<u64> {
     tri!(LOG.checked_add(tri!(LOGDATA.checked_mul(len)))).checked_add(LOGTOPIC * n as u64)
 }
 
+/// `LOG` opcode cost without memory expansion.
+///
+/// Saturates at [`u64::MAX`] on overflow, which can't be paid for.
+#[inline]
+pub const fn log_gas(topic_count: u8, data_len: usize) -> u64 {
+    LOG.saturating_add(LOGTOPIC * topic_count as u64)
+        .saturating_add(LOGDATA.saturating_mul(data_len as u64))
+}
+
 /// `KECCAK256` opcode cost calculation.
 #[inline]
 pub const fn keccak256_cost(len: usize) -> Option<u64> {
@@ -286,19 +297,43 @@ pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResul
 /// as they were present before SPURIOUS_DRAGON hardfork.
 #[inline]
 pub const fn call_cost(spec_id: SpecId, transfers_value: bool, account_load: AccountLoad) -> u64 {
+    call_gas_breakdown(spec_id, transfers_value, account_load).cost()
+}
+
+/// Splits the [`call_cost`] into its components.
+///
+/// Only the charged components are set, `stipend`, `forwarded` and `returned` are zero.
+#[inline]
+pub const fn call_gas_breakdown(
+    spec_id: SpecId,
+    transfers_value: bool,
+    account_load: AccountLoad,
+) -> CallGasBreakdown {
+    let mut breakdown = CallGasBreakdown {
+        base: 0,
+        access: 0,
+        value_transfer: 0,
+        new_account: 0,
+        stipend: 0,
+        forwarded: 0,
+        returned: 0,
+    };
+
     // Account access.
-    let mut gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost_with_delegation(account_load.load)
+    if spec_id.is_enabled_in(SpecId::BERLIN) {
+        breakdown.base = WARM_STORAGE_READ_COST;
+        breakdown.access =
+            warm_cold_cost_with_delegation(account_load.load) - WARM_STORAGE_READ_COST;
     } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
         // EIP-150: Gas cost changes for IO-heavy operations
-        700
+        breakdown.base = 700;
     } else {
-        40
-    };
+        breakdown.base = 40;
+    }
 
     // Transfer value cost
     if transfers_value {
-        gas += CALLVALUE;
+        breakdown.value_transfer = CALLVALUE;
     }
 
     // New account cost
@@ -307,14 +342,154 @@ pub const fn call_cost(spec_id: SpecId, transfers_value: bool, account_load: Acc
         if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
             // Account only if there is value transferred.
             if transfers_value {
-                gas += NEWACCOUNT;
+                breakdown.new_account = NEWACCOUNT;
             }
         } else {
-            gas += NEWACCOUNT;
+            breakdown.new_account = NEWACCOUNT;
         }
     }
 
-    gas
+    breakdown
+}
+
+/// Calculates the non-forwarded part of the `CALL` gas cost.
+///
+/// Sums the account access, value transfer and new account costs, without any
+/// EIP-7702 delegation. This is charged before the 63/64 rule is applied to the
+/// forwarded gas. See [`call_cost`] for the details of each component.
+#[inline]
+pub fn call_base_gas(
+    is_warm: bool,
+    transfers_value: bool,
+    new_account: bool,
+    spec_id: SpecId,
+) -> u64 {
+    call_cost(
+        spec_id,
+        transfers_value,
+        AccountLoad {
+            load: Eip7702CodeLoad::new_not_delegated((), !is_warm),
+            is_empty: new_account,
+        },
+    )
+}
+
+/// Returns the fixed gas cost of `opcode` in the given spec.
+///
+/// Returns `None` if the cost depends on runtime values (memory expansion, account
+/// or storage access, call and create costs...), if the opcode is unknown, or if it
+/// is not [enabled][bytecode::opcode::OpCodeInfo::enabled_in] in `spec_id`.
+pub const fn static_gas_cost(opcode: u8, spec_id: SpecId) -> Option<u64> {
+    use bytecode::opcode as op;
+
+    match OpCode::info_by_op(opcode) {
+        Some(info) if info.is_enabled_in(spec_id) => {}
+        _ => return None,
+    }
+
+    let gas = match opcode {
+        op::STOP => ZERO,
+        op::JUMPDEST => JUMPDEST,
+        op::ADDRESS
+        | op::ORIGIN
+        | op::CALLER
+        | op::CALLVALUE
+        | op::CALLDATASIZE
+        | op::CODESIZE
+        | op::GASPRICE
+        | op::RETURNDATASIZE
+        | op::COINBASE
+        | op::TIMESTAMP
+        | op::NUMBER
+        | op::DIFFICULTY
+        | op::GASLIMIT
+        | op::CHAINID
+        | op::BASEFEE
+        | op::BLOBBASEFEE
+        | op::POP
+        | op::PC
+        | op::MSIZE
+        | op::GAS
+        | op::PUSH0
+        | op::DATASIZE
+        | op::RJUMP => BASE,
+        op::ADD
+        | op::SUB
+        | op::LT
+        | op::GT
+        | op::SLT
+        | op::SGT
+        | op::EQ
+        | op::ISZERO
+        | op::AND
+        | op::OR
+        | op::XOR
+        | op::NOT
+        | op::BYTE
+        | op::SHL
+        | op::SHR
+        | op::SAR
+        | op::CALLDATALOAD
+        | op::BLOBHASH
+        | op::DATALOADN
+        | op::RETURNDATALOAD
+        | op::DUPN
+        | op::SWAPN
+        | op::EXCHANGE => VERYLOW,
+        op::PUSH1..=op::PUSH32 | op::DUP1..=op::DUP16 | op::SWAP1..=op::SWAP16 => VERYLOW,
+        op::RJUMPI | op::RJUMPV => CONDITION_JUMP_GAS,
+        op::RETF => RETF_GAS,
+        op::DATALOAD => DATA_LOAD_GAS,
+        op::MUL
+        | op::DIV
+        | op::SDIV
+        | op::MOD
+        | op::SMOD
+        | op::SIGNEXTEND
+        | op::SELFBALANCE
+        | op::CALLF
+        | op::JUMPF => LOW,
+        op::ADDMOD | op::MULMOD | op::JUMP => MID,
+        op::JUMPI => HIGH,
+        op::BLOCKHASH => BLOCKHASH,
+        op::TLOAD | op::TSTORE => WARM_STORAGE_READ_COST,
+        _ => return None,
+    };
+    Some(gas)
+}
+
+/// Returns `true` if the gas cost of the known `opcode` depends on runtime values.
+///
+/// The [static gas cost][static_gas_cost] of these opcodes is `None` in every spec they
+/// are enabled in. Unknown opcodes return `false`.
+pub const fn is_dynamic_gas_cost(opcode: u8) -> bool {
+    match OpCode::info_by_op(opcode) {
+        Some(info) => static_gas_cost(opcode, info.enabled_in()).is_none(),
+        None => false,
+    }
+}
+
+/// Returns the fixed gas cost of every opcode in legacy `code`.
+///
+/// Each entry holds the program counter, the opcode and its [static gas cost][static_gas_cost].
+/// `PUSH` immediates are skipped, unknown and EOF-only opcodes are not included.
+pub fn static_gas_costs(code: &[u8], spec_id: SpecId) -> Vec<(usize, OpCode, Option<u64>)> {
+    let mut costs = Vec::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let byte = code[pc];
+        match OpCode::new(byte) {
+            Some(opcode) if !opcode.info().is_eof_only() => {
+                costs.push((pc, opcode, static_gas_cost(byte, spec_id)));
+            }
+            _ => {}
+        }
+        pc += 1;
+        if OpCode::is_push_by_op(byte) {
+            pc += (byte - bytecode::opcode::PUSH0) as usize;
+        }
+    }
+    costs
 }
 
 /// Berlin warm and cold storage access cost for account access.
@@ -342,10 +517,19 @@ pub const fn warm_cold_cost_with_delegation(load: Eip7702CodeLoad<()>) -> u64 {
 /// Memory expansion cost calculation for a given number of words.
 #[inline]
 pub const fn memory_gas(num_words: usize) -> u64 {
+    memory_gas_with_quad_coeff_div(num_words, MEMORY_QUAD_COEFF_DIV)
+}
+
+/// Memory expansion cost calculation for a given number of words with a custom
+/// denominator of the quadratic part.
+///
+/// The standard denominator is [`MEMORY_QUAD_COEFF_DIV`].
+#[inline]
+pub const fn memory_gas_with_quad_coeff_div(num_words: usize, quad_coeff_div: u64) -> u64 {
     let num_words = num_words as u64;
     MEMORY
         .saturating_mul(num_words)
-        .saturating_add(num_words.saturating_mul(num_words) / 512)
+        .saturating_add(num_words.saturating_mul(num_words) / quad_coeff_div)
 }
 
 /// Initial gas that is deducted for transaction to be included.
@@ -403,3 +587,143 @@ pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
 
     initial_gas
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_gas_breakdown_cold_new_account() {
+        let account_load = AccountLoad {
+            load: Eip7702CodeLoad::new_not_delegated((), true),
+            is_empty: true,
+        };
+        let breakdown = call_gas_breakdown(SpecId::CANCUN, true, account_load.clone());
+        assert_eq!(breakdown.base, WARM_STORAGE_READ_COST);
+        assert_eq!(
+            breakdown.access,
+            COLD_ACCOUNT_ACCESS_COST - WARM_STORAGE_READ_COST
+        );
+        assert_eq!(breakdown.value_transfer, CALLVALUE);
+        assert_eq!(breakdown.new_account, NEWACCOUNT);
+        assert_eq!(breakdown.cost(), 2_600 + 9_000 + 25_000);
+        assert_eq!(
+            breakdown.cost(),
+            call_cost(SpecId::CANCUN, true, account_load.clone())
+        );
+
+        // No new account cost without value since Spurious Dragon.
+        let breakdown = call_gas_breakdown(SpecId::CANCUN, false, account_load.clone());
+        assert_eq!(breakdown.new_account, 0);
+        let breakdown = call_gas_breakdown(SpecId::HOMESTEAD, false, account_load);
+        assert_eq!((breakdown.base, breakdown.new_account), (40, NEWACCOUNT));
+    }
+
+    #[test]
+    fn log_gas_components() {
+        assert_eq!(log_gas(0, 10), 375 + 8 * 10);
+        assert_eq!(log_gas(4, 0), 375 + 4 * 375);
+
+        // Per topic and per byte components.
+        assert_eq!(log_gas(2, 0) - log_gas(1, 0), LOGTOPIC);
+        assert_eq!(log_gas(1, 33) - log_gas(1, 32), LOGDATA);
+        assert_eq!(log_gas(0, 0), LOG);
+
+        assert_eq!(log_gas(4, usize::MAX), u64::MAX);
+        assert_eq!(Some(log_gas(3, 100)), log_cost(3, 100));
+    }
+
+    #[test]
+    fn static_gas_costs_push_add() {
+        use bytecode::opcode::{ADD, PUSH1};
+
+        let costs = static_gas_costs(&[PUSH1, 0x01, PUSH1, 0x02, ADD], SpecId::CANCUN);
+        assert_eq!(
+            costs,
+            [
+                (0, OpCode::PUSH1, Some(3)),
+                (2, OpCode::PUSH1, Some(3)),
+                (4, OpCode::ADD, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dynamic_gas_cost() {
+        use bytecode::opcode::{ADD, CALL, EXP, KECCAK256, MSTORE, PUSH1, SLOAD, TLOAD};
+
+        for opcode in [SLOAD, CALL, EXP, KECCAK256, MSTORE] {
+            assert!(is_dynamic_gas_cost(opcode), "{opcode:#x}");
+            assert_eq!(static_gas_cost(opcode, SpecId::CANCUN), None);
+        }
+        for opcode in [ADD, PUSH1, TLOAD] {
+            assert!(!is_dynamic_gas_cost(opcode), "{opcode:#x}");
+        }
+        assert!(!is_dynamic_gas_cost(0x0C));
+
+        // Not enabled yet, but not dynamic either.
+        assert_eq!(static_gas_cost(TLOAD, SpecId::SHANGHAI), None);
+        assert_eq!(static_gas_cost(TLOAD, SpecId::CANCUN), Some(100));
+    }
+
+    #[test]
+    fn static_gas_cost_follows_opcode_info() {
+        for opcode in 0..=u8::MAX {
+            let Some(info) = OpCode::info_by_op(opcode) else {
+                assert_eq!(static_gas_cost(opcode, SpecId::LATEST), None);
+                assert!(!is_dynamic_gas_cost(opcode));
+                continue;
+            };
+            for spec in (0..=SpecId::OSAKA as u8)
+                .filter_map(SpecId::try_from_u8)
+                .chain([SpecId::LATEST])
+            {
+                let cost = static_gas_cost(opcode, spec);
+                if !info.is_enabled_in(spec) || is_dynamic_gas_cost(opcode) {
+                    assert_eq!(cost, None, "{} in {spec:?}", info.name());
+                } else {
+                    assert!(cost.is_some(), "{} in {spec:?}", info.name());
+                }
+            }
+            if info.is_eof_only() {
+                assert_eq!(static_gas_cost(opcode, SpecId::FRONTIER), None);
+                assert!(static_gas_costs(&[opcode], SpecId::LATEST).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn call_base_gas_warm_no_value() {
+        assert_eq!(
+            call_base_gas(true, false, false, SpecId::CANCUN),
+            WARM_STORAGE_READ_COST
+        );
+        // New account is charged only with value transfer after Spurious Dragon.
+        assert_eq!(
+            call_base_gas(true, false, true, SpecId::CANCUN),
+            WARM_STORAGE_READ_COST
+        );
+    }
+
+    #[test]
+    fn call_base_gas_cold_value_new_account() {
+        let gas = call_base_gas(false, true, true, SpecId::CANCUN);
+        assert_eq!(gas, COLD_ACCOUNT_ACCESS_COST + CALLVALUE + NEWACCOUNT);
+        assert_eq!(gas, 36_600);
+
+        assert_eq!(
+            call_base_gas(false, true, true, SpecId::CANCUN)
+                - call_base_gas(false, true, false, SpecId::CANCUN),
+            NEWACCOUNT
+        );
+        assert_eq!(
+            call_base_gas(false, true, false, SpecId::CANCUN)
+                - call_base_gas(false, false, false, SpecId::CANCUN),
+            CALLVALUE
+        );
+        assert_eq!(
+            call_base_gas(false, true, true, SpecId::ISTANBUL),
+            700 + CALLVALUE + NEWACCOUNT
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub const CALLVALUE: u64 = 9000;
 pub const NEWACCOUNT: u64 = 25000;
 pub const EXP: u64 = 10;
 pub const MEMORY: u64 = 3;
+/// Denominator of the quadratic part of the memory expansion cost.
+pub const MEMORY_QUAD_COEFF_DIV: u64 = 512;
 pub const LOG: u64 = 375;
 pub const LOGDATA: u64 = 8;
 pub const LOGTOPIC: u64 = 375;
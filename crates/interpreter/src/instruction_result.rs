@@ -52,6 +52,8 @@ pub enum InstructionResult {
     InvalidOperandOOG,
     /// Out of gas error encountered while checking for reentrancy sentry.
     ReentrancySentryOOG,
+    /// Gas spent by a single instruction exceeded the configured limit.
+    OpcodeGasLimitOOG,
     /// Unknown or invalid opcode.
     OpcodeNotFound,
     /// Invalid `CALL` with value transfer in static context.
@@ -98,6 +100,10 @@ pub enum InstructionResult {
     EofAuxDataTooSmall,
     /// `EXT*CALL` target address needs to be padded with 0s.
     InvalidEXTCALLTarget,
+    /// Number of executed instructions exceeded the configured limit.
+    OutOfInstructions,
+    /// Sub call returned more gas than the caller had spent.
+    GasUnderflow,
 }
 
 impl From<TransferError> for InstructionResult {
@@ -133,6 +139,7 @@ impl From<HaltReason> for InstructionResult {
                 OutOfGasError::MemoryLimit => Self::MemoryLimitOOG,
                 OutOfGasError::Precompile => Self::PrecompileOOG,
                 OutOfGasError::ReentrancySentry => Self::ReentrancySentryOOG,
+                OutOfGasError::OpcodeGasLimit => Self::OpcodeGasLimitOOG,
             },
             HaltReason::OpcodeNotFound => Self::OpcodeNotFound,
             HaltReason::InvalidFEOpcode => Self::InvalidFEOpcode,
@@ -156,6 +163,8 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
             HaltReason::SubRoutineStackOverflow => Self::SubRoutineStackOverflow,
             HaltReason::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            HaltReason::OutOfInstructions => Self::OutOfInstructions,
+            HaltReason::GasUnderflow => Self::GasUnderflow,
         }
     }
 }
@@ -192,6 +201,7 @@ macro_rules! return_error {
             | $crate::InstructionResult::PrecompileOOG
             | $crate::InstructionResult::InvalidOperandOOG
             | $crate::InstructionResult::ReentrancySentryOOG
+            | $crate::InstructionResult::OpcodeGasLimitOOG
             | $crate::InstructionResult::OpcodeNotFound
             | $crate::InstructionResult::CallNotAllowedInsideStatic
             | $crate::InstructionResult::StateChangeDuringStaticCall
@@ -215,6 +225,8 @@ macro_rules! return_error {
             | $crate::InstructionResult::EofAuxDataTooSmall
             | $crate::InstructionResult::EofAuxDataOverflow
             | $crate::InstructionResult::InvalidEXTCALLTarget
+            | $crate::InstructionResult::OutOfInstructions
+            | $crate::InstructionResult::GasUnderflow
     };
 }
 
@@ -344,6 +356,9 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::ReentrancySentryOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::ReentrancySentry).into())
             }
+            InstructionResult::OpcodeGasLimitOOG => {
+                Self::Halt(HaltReason::OutOfGas(OutOfGasError::OpcodeGasLimit).into())
+            }
             InstructionResult::OpcodeNotFound | InstructionResult::ReturnContractInNotInitEOF => {
                 Self::Halt(HaltReason::OpcodeNotFound.into())
             }
@@ -389,6 +404,10 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::InvalidEXTCALLTarget => {
                 Self::Halt(HaltReason::InvalidEXTCALLTarget.into())
             }
+            InstructionResult::OutOfInstructions => {
+                Self::Halt(HaltReason::OutOfInstructions.into())
+            }
+            InstructionResult::GasUnderflow => Self::Halt(HaltReason::GasUnderflow.into()),
             InstructionResult::InvalidExtDelegateCallTarget => {
                 Self::Internal(InternalResult::InvalidExtDelegateCallTarget)
             }
@@ -443,6 +462,7 @@ mod tests {
             InstructionResult::MemoryLimitOOG,
             InstructionResult::PrecompileOOG,
             InstructionResult::InvalidOperandOOG,
+            InstructionResult::OpcodeGasLimitOOG,
             InstructionResult::OpcodeNotFound,
             InstructionResult::CallNotAllowedInsideStatic,
             InstructionResult::StateChangeDuringStaticCall,
@@ -460,6 +480,8 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::OutOfInstructions,
+            InstructionResult::GasUnderflow,
         ];
 
         for result in error_results {
@@ -13,6 +13,8 @@ pub mod i256;
 pub mod memory;
 pub mod stack;
 pub mod system;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod tx_info;
 pub mod utility;
 
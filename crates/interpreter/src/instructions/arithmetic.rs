@@ -5,33 +5,46 @@ use crate::{
     interpreter_types::{InterpreterTypes, LoopControl, RuntimeFlag, StackTrait},
     Host,
 };
+use bytecode::opcode;
 use primitives::U256;
 
 pub fn add<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::VERYLOW);
     popn_top!([op1], op2, interpreter);
-    *op2 = op1.wrapping_add(*op2);
+    let (result, wrapped) = op1.overflowing_add(*op2);
+    if wrapped {
+        host.arithmetic_wrap(opcode::ADD);
+    }
+    *op2 = result;
 }
 
 pub fn mul<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::LOW);
     popn_top!([op1], op2, interpreter);
-    *op2 = op1.wrapping_mul(*op2);
+    let (result, wrapped) = op1.overflowing_mul(*op2);
+    if wrapped {
+        host.arithmetic_wrap(opcode::MUL);
+    }
+    *op2 = result;
 }
 
 pub fn sub<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::VERYLOW);
     popn_top!([op1], op2, interpreter);
-    *op2 = op1.wrapping_sub(*op2);
+    let (result, wrapped) = op1.overflowing_sub(*op2);
+    if wrapped {
+        host.arithmetic_wrap(opcode::SUB);
+    }
+    *op2 = result;
 }
 
 pub fn div<WIRE: InterpreterTypes, H: Host + ?Sized>(
@@ -146,3 +159,30 @@ pub fn signextend<WIRE: InterpreterTypes, H: Host + ?Sized>(
         *x = if bit { *x | !mask } else { *x & mask };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run, TestHost},
+        InstructionResult,
+    };
+    use bytecode::opcode::*;
+    use primitives::U256;
+
+    #[test]
+    fn add_wrap_is_tracked() {
+        let mut code = vec![PUSH32];
+        code.extend([0xFF; 32]);
+        code.extend([PUSH1, 0x01, ADD, PUSH1, 0x01, PUSH1, 0x01, ADD]);
+
+        let mut host = TestHost::default().with_wrap_tracking();
+        let interp = run(&code, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::ZERO, U256::from(2)]);
+        assert_eq!(host.arithmetic_wraps, Some(1));
+
+        let mut host = TestHost::default();
+        let _ = run(&code, &mut host);
+        assert_eq!(host.arithmetic_wraps, None);
+    }
+}
@@ -84,8 +84,45 @@ pub fn blob_basefee<WIRE: InterpreterTypes, H: Host + ?Sized>(
 ) {
     check!(interpreter, CANCUN);
     gas!(interpreter, gas::BASE);
-    push!(
-        interpreter,
-        U256::from(host.block().blob_gasprice().unwrap_or_default())
-    );
+    push!(interpreter, host.blob_base_fee());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run_with_spec, TestHost},
+        InstructionResult,
+    };
+    use bytecode::opcode::*;
+    use primitives::U256;
+    use specification::hardfork::SpecId;
+
+    #[test]
+    fn blob_basefee() {
+        let mut host = TestHost::default();
+        host.block.set_blob_excess_gas_and_price(10_000_000);
+        let blob_gasprice = host
+            .block
+            .blob_excess_gas_and_price
+            .as_ref()
+            .unwrap()
+            .blob_gasprice;
+        assert!(blob_gasprice > 1);
+
+        let interp = run_with_spec(&[BLOBBASEFEE], SpecId::CANCUN, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from(blob_gasprice)]);
+        assert_eq!(interp.control.gas.spent(), 2);
+    }
+
+    #[test]
+    fn blob_basefee_pre_cancun() {
+        let mut host = TestHost::default();
+        let interp = run_with_spec(&[BLOBBASEFEE], SpecId::SHANGHAI, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::NotActivated
+        );
+        assert!(interp.stack.is_empty());
+    }
 }
@@ -2,6 +2,7 @@ use crate::{
     gas,
     interpreter::Interpreter,
     interpreter_types::{InterpreterTypes, LoopControl, MemoryTrait, RuntimeFlag, StackTrait},
+    CallGasBreakdown,
 };
 use context_interface::journaled_state::AccountLoad;
 use core::{cmp::min, ops::Range};
@@ -51,12 +52,12 @@ pub fn calc_call_gas(
     has_transfer: bool,
     local_gas_limit: u64,
 ) -> Option<u64> {
-    let call_cost = gas::call_cost(
+    let breakdown = gas::call_gas_breakdown(
         interpreter.runtime_flag.spec_id(),
         has_transfer,
         account_load,
     );
-    gas!(interpreter, call_cost, None);
+    gas!(interpreter, breakdown.cost(), None);
 
     // EIP-150: Gas cost changes for IO-heavy operations
     let gas_limit = if interpreter.runtime_flag.spec_id().is_enabled_in(TANGERINE) {
@@ -69,5 +70,10 @@ pub fn calc_call_gas(
         local_gas_limit
     };
 
+    interpreter.call_gas = Some(CallGasBreakdown {
+        stipend: if has_transfer { gas::CALL_STIPEND } else { 0 },
+        forwarded: gas_limit,
+        ..breakdown
+    });
     Some(gas_limit)
 }
@@ -270,6 +270,68 @@ pub fn unknown<WIRE: InterpreterTypes, H: Host + ?Sized>(
         .set_instruction_result(InstructionResult::OpcodeNotFound);
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{eof, non_returning, run_eof},
+        interpreter::EthInterpreter,
+        InstructionResult, Interpreter,
+    };
+    use bytecode::{
+        eof::TypesSection,
+        opcode::{CALLF, JUMPF, PUSH1, RETF, STOP},
+    };
+    use primitives::{Bytes, U256};
+
+    /// Runs `main` as the non-returning first code section and `function` as the second one.
+    fn run(main: &[u8], function: &[u8]) -> Interpreter<EthInterpreter> {
+        run_eof(eof(
+            &[
+                (non_returning(1), main),
+                (TypesSection::default(), function),
+            ],
+            Bytes::new(),
+        ))
+    }
+
+    #[test]
+    fn callf_retf() {
+        let interp = run(&[CALLF, 0x00, 0x01, PUSH1, 0x2a, STOP], &[RETF]);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        // Execution resumed after `CALLF` and the return frame was popped.
+        assert_eq!(interp.stack.data(), &vec![U256::from(0x2a)]);
+        assert!(interp.sub_routine.is_empty());
+        assert_eq!(interp.sub_routine.current_code_idx, 0);
+        // `CALLF`, `RETF` and `PUSH1`.
+        assert_eq!(interp.control.gas.spent(), 5 + 3 + 3);
+    }
+
+    #[test]
+    fn jumpf() {
+        let interp = run(
+            &[JUMPF, 0x00, 0x01, PUSH1, 0x01, STOP],
+            &[PUSH1, 0x2a, STOP],
+        );
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::from(0x2a)]);
+        // Tail call switches the code section without pushing a return frame.
+        assert!(interp.sub_routine.is_empty());
+        assert_eq!(interp.sub_routine.current_code_idx, 1);
+        // `JUMPF` and `PUSH1`.
+        assert_eq!(interp.control.gas.spent(), 5 + 3);
+    }
+
+    #[test]
+    fn callf_overflow() {
+        let interp = run(&[CALLF, 0x00, 0x01, STOP], &[CALLF, 0x00, 0x01, RETF]);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::SubRoutineStackOverflow
+        );
+        assert_eq!(interp.sub_routine.len(), 1024);
+    }
+}
+
 // TODO : Test
 /*
 #[cfg(test)]
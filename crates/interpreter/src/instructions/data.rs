@@ -58,7 +58,7 @@ pub fn data_size<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
 pub fn data_copy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     require_eof!(interpreter);
     gas!(interpreter, VERYLOW);
@@ -80,7 +80,66 @@ pub fn data_copy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let data = interpreter.bytecode.data();
 
     // Set data from the eof to the shared memory. Padded it with zeros.
-    interpreter.memory.set_data(mem_offset, offset, size, data);
+    let data = data.get(offset..).unwrap_or_default();
+    if let Err(result) = interpreter
+        .memory
+        .copy_from_slice_checked(mem_offset, data, size)
+    {
+        interpreter.control.set_instruction_result(result);
+        return;
+    }
+    host.trace_memory_write(
+        mem_offset,
+        interpreter.memory.slice_len(mem_offset, size).as_ref(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{eof, legacy, run_bytecode, run_eof, TestHost},
+        InstructionResult,
+    };
+    use bytecode::{
+        eof::TypesSection,
+        opcode::{DATALOADN, STOP},
+    };
+    use primitives::{b256, bytes, Bytes, U256};
+    use specification::hardfork::SpecId;
+
+    #[test]
+    fn dataloadn() {
+        let data = bytes!("00000000000000000000000000000000000000000000000000000000000000010203");
+        let code = Bytes::from([DATALOADN, 0x00, 0x00, DATALOADN, 0x00, 0x21, STOP]);
+
+        let interp = run_eof(eof(&[(TypesSection::default(), &code)], data));
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.control.gas.spent(), 6);
+        assert_eq!(
+            interp.stack.data(),
+            &vec![
+                // Fully inside the data section.
+                U256::from(1),
+                // Straddling the end, zero padded.
+                b256!("0300000000000000000000000000000000000000000000000000000000000000").into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dataloadn_legacy() {
+        let interp = run_bytecode(
+            legacy(&[DATALOADN, 0x00, 0x00]),
+            SpecId::OSAKA,
+            false,
+            &mut TestHost::default(),
+        );
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::EOFOpcodeDisabledInLegacy
+        );
+        assert!(interp.stack.data().is_empty());
+    }
 }
 
 // TODO : Test
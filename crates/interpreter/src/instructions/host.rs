@@ -133,10 +133,18 @@ pub fn extcodecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let code_offset = min(as_usize_saturated!(code_offset), code.len());
     resize_memory!(interpreter, memory_offset, len);
 
-    // Note: This can't panic because we resized memory to fit.
-    interpreter
-        .memory
-        .set_data(memory_offset, code_offset, len, &code);
+    if let Err(result) =
+        interpreter
+            .memory
+            .copy_from_slice_checked(memory_offset, &code[code_offset..], len)
+    {
+        interpreter.control.set_instruction_result(result);
+        return;
+    }
+    host.trace_memory_write(
+        memory_offset,
+        interpreter.memory.slice_len(memory_offset, len).as_ref(),
+    );
 }
 
 pub fn blockhash<WIRE: InterpreterTypes, H: Host + ?Sized>(
@@ -206,10 +214,11 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
         )
     );
 
-    interpreter.control.gas().record_refund(gas::sstore_refund(
-        interpreter.runtime_flag.spec_id(),
-        &state_load.data,
-    ));
+    let refund = gas::sstore_refund(interpreter.runtime_flag.spec_id(), &state_load.data);
+    if refund != 0 {
+        interpreter.control.gas().record_refund(refund);
+        host.trace_refund(refund, interpreter.control.gas().refunded());
+    }
 }
 
 /// EIP-1153: Transient storage opcodes
@@ -249,7 +258,7 @@ pub fn log<const N: usize, H: Host + ?Sized>(
 
     popn!([offset, len], interpreter);
     let len = as_usize_or_fail!(interpreter, len);
-    gas_or_fail!(interpreter, gas::log_cost(N as u8, len as u64));
+    gas!(interpreter, gas::log_gas(N as u8, len));
     let data = if len == 0 {
         Bytes::new()
     } else {
@@ -295,7 +304,8 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // EIP-3529: Reduction in refunds
     if !interpreter.runtime_flag.spec_id().is_enabled_in(LONDON) && !res.previously_destroyed {
-        interpreter.control.gas().record_refund(gas::SELFDESTRUCT)
+        interpreter.control.gas().record_refund(gas::SELFDESTRUCT);
+        host.trace_refund(gas::SELFDESTRUCT, interpreter.control.gas().refunded());
     }
     gas!(
         interpreter,
@@ -306,3 +316,273 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
         .control
         .set_instruction_result(InstructionResult::SelfDestruct);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run, run_static, TestHost},
+        CallGasBreakdown, Host, InstructionResult, SStoreResult, SelfDestructResult, StateLoad,
+    };
+    use bytecode::opcode::*;
+    use context::{BlockEnv, CfgEnv, TxEnv};
+    use context_interface::journaled_state::{AccountLoad, Eip7702CodeLoad, JournalCheckpoint};
+    use primitives::{Address, Bytes, Log, B256, U256};
+    use std::vec::Vec;
+
+    #[test]
+    fn blockhash_from_history() {
+        let hashes = [
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        ];
+        let mut host = TestHost::default()
+            .with_block_hashes((1..=3).zip(hashes).collect::<std::vec::Vec<_>>());
+
+        let interp = run(
+            &[
+                PUSH1, 0x00, BLOCKHASH, PUSH1, 0x01, BLOCKHASH, PUSH1, 0x02, BLOCKHASH, PUSH1,
+                0x03, BLOCKHASH, PUSH1, 0x04, BLOCKHASH,
+            ],
+            &mut host,
+        );
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            interp.stack.data(),
+            &[
+                U256::ZERO,
+                U256::from_be_bytes(hashes[0].0),
+                U256::from_be_bytes(hashes[1].0),
+                U256::from_be_bytes(hashes[2].0),
+                U256::ZERO,
+            ]
+        );
+    }
+
+    #[test]
+    fn state_changes_fail_in_static_context() {
+        let writes: [&[u8]; 10] = [
+            &[PUSH1, 0x01, PUSH1, 0x00, SSTORE],
+            &[PUSH1, 0x01, PUSH1, 0x00, TSTORE],
+            &[PUSH1, 0x00, PUSH1, 0x00, LOG0],
+            &[PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, LOG1],
+            &[PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, LOG2],
+            &[
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, LOG3,
+            ],
+            &[
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, LOG4,
+            ],
+            &[PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, CREATE],
+            &[PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, CREATE2],
+            &[PUSH1, 0x00, SELFDESTRUCT],
+        ];
+        for code in writes {
+            let interp = run_static(code, &mut TestHost::default());
+            assert_eq!(
+                interp.control.instruction_result,
+                InstructionResult::StateChangeDuringStaticCall,
+                "{:?}",
+                code
+            );
+        }
+
+        // `CALL` transferring value.
+        let interp = run_static(
+            &[
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01, PUSH1, 0x00, GAS,
+                CALL,
+            ],
+            &mut TestHost::default(),
+        );
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::CallNotAllowedInsideStatic
+        );
+
+        // Reads are allowed.
+        let interp = run_static(&[PUSH1, 0x00, SLOAD], &mut TestHost::default());
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+
+        // `CALL` without value is allowed.
+        let interp = run_static(
+            &[
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, GAS,
+                CALL,
+            ],
+            &mut TestHost::default(),
+        );
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::CallOrCreate
+        );
+    }
+
+    #[test]
+    fn call_records_gas_breakdown() {
+        // `CALL` with 1 wei and 10_000 gas to a warm, existing account.
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01, PUSH1, 0x00, PUSH2,
+            0x27, 0x10, CALL,
+        ];
+        let mut interp = run(&code, &mut TestHost::default());
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::CallOrCreate
+        );
+        assert_eq!(
+            interp.take_call_gas(),
+            Some(CallGasBreakdown {
+                base: 100,
+                access: 0,
+                value_transfer: 9_000,
+                new_account: 0,
+                stipend: 2_300,
+                forwarded: 10_000,
+                returned: 0,
+            })
+        );
+        assert_eq!(interp.take_call_gas(), None);
+    }
+
+    #[test]
+    fn dummy_host_checkpoint() {
+        let mut host = TestHost::default();
+        let checkpoint = host.checkpoint();
+        let _ = run(
+            &[
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x02, PUSH1, 0x00, TSTORE, PUSH1, 0x00,
+                PUSH1, 0x00, LOG0,
+            ],
+            &mut host,
+        );
+        // Nested checkpoints are reverted together with the outer one.
+        let _ = host.checkpoint();
+        let _ = run(&[PUSH1, 0x03, PUSH1, 0x01, SSTORE], &mut host);
+        assert_eq!(host.storage.len(), 2);
+        assert_eq!(host.log.len(), 1);
+
+        host.checkpoint_revert(checkpoint);
+        assert!(host.storage.is_empty());
+        assert!(host.transient_storage.is_empty());
+        assert!(host.log.is_empty());
+
+        let _ = host.checkpoint();
+        let _ = run(&[PUSH1, 0x01, PUSH1, 0x00, SSTORE], &mut host);
+        host.checkpoint_commit();
+        assert_eq!(host.storage.get(&U256::ZERO), Some(&U256::from(1)));
+    }
+
+    /// Host that records refund deltas reported through [`Host::trace_refund`].
+    #[derive(Default)]
+    struct RefundTracer {
+        inner: TestHost,
+        refunds: Vec<(i64, i64)>,
+    }
+
+    impl Host for RefundTracer {
+        type BLOCK = BlockEnv;
+        type TX = TxEnv;
+        type CFG = CfgEnv;
+
+        fn tx(&self) -> &Self::TX {
+            self.inner.tx()
+        }
+
+        fn block(&self) -> &Self::BLOCK {
+            self.inner.block()
+        }
+
+        fn cfg(&self) -> &Self::CFG {
+            self.inner.cfg()
+        }
+
+        fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad> {
+            self.inner.load_account_delegated(address)
+        }
+
+        fn block_hash(&mut self, number: u64) -> Option<B256> {
+            self.inner.block_hash(number)
+        }
+
+        fn balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
+            self.inner.balance(address)
+        }
+
+        fn code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+            self.inner.code(address)
+        }
+
+        fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+            self.inner.code_hash(address)
+        }
+
+        fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+            self.inner.sload(address, index)
+        }
+
+        fn sstore(
+            &mut self,
+            address: Address,
+            index: U256,
+            value: U256,
+        ) -> Option<StateLoad<SStoreResult>> {
+            self.inner.sstore(address, index, value)
+        }
+
+        fn tload(&mut self, address: Address, index: U256) -> U256 {
+            self.inner.tload(address, index)
+        }
+
+        fn tstore(&mut self, address: Address, index: U256, value: U256) {
+            self.inner.tstore(address, index, value)
+        }
+
+        fn log(&mut self, log: Log) {
+            self.inner.log(log)
+        }
+
+        fn selfdestruct(
+            &mut self,
+            address: Address,
+            target: Address,
+        ) -> Option<StateLoad<SelfDestructResult>> {
+            self.inner.selfdestruct(address, target)
+        }
+        fn checkpoint(&mut self) -> JournalCheckpoint {
+            self.inner.checkpoint()
+        }
+
+        fn checkpoint_commit(&mut self) {
+            self.inner.checkpoint_commit()
+        }
+
+        fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
+            self.inner.checkpoint_revert(checkpoint)
+        }
+
+        fn trace_refund(&mut self, delta: i64, new_total: i64) {
+            self.refunds.push((delta, new_total));
+        }
+    }
+
+    #[test]
+    fn sstore_clear_traces_refund() {
+        let mut host = RefundTracer::default();
+        let interp = run(
+            &[
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, SSTORE,
+            ],
+            &mut host,
+        );
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+
+        assert!(!host.refunds.is_empty());
+        let total: i64 = host.refunds.iter().map(|(delta, _)| delta).sum();
+        assert_eq!(total, interp.control.gas.refunded());
+        assert_eq!(
+            host.refunds.last().unwrap().1,
+            interp.control.gas.refunded()
+        );
+    }
+}
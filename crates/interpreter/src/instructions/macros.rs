@@ -107,23 +107,20 @@ macro_rules! resize_memory {
         $crate::resize_memory!($interpreter, $offset, $len, ())
     };
     ($interpreter:expr, $offset:expr, $len:expr, $ret:expr) => {
-        let words_num = $crate::interpreter::num_words($offset.saturating_add($len));
-        match $interpreter
-            .control
-            .gas()
-            .record_memory_expansion(words_num)
-        {
-            $crate::gas::MemoryExtensionResult::Extended => {
-                $interpreter.memory.resize(words_num * 32);
-            }
-            $crate::gas::MemoryExtensionResult::OutOfGas => {
-                $interpreter
-                    .control
-                    .set_instruction_result($crate::InstructionResult::OutOfGas);
-                return $ret;
+        let new_size = $crate::interpreter::num_words($offset.saturating_add($len)) * 32;
+        if new_size > $interpreter.memory.size() {
+            let gas = $interpreter.control.gas();
+            let new_memory_cost = gas.memory_expansion_cost(new_size / 32);
+            match gas.record_memory_gas(new_memory_cost) {
+                $crate::InstructionResult::Continue => {
+                    $interpreter.memory.resize(new_size);
+                }
+                result => {
+                    $interpreter.control.set_instruction_result(result);
+                    return $ret;
+                }
             }
-            $crate::gas::MemoryExtensionResult::Same => (), // no action
-        };
+        }
     };
 }
 
@@ -15,29 +15,33 @@ pub fn mload<WIRE: InterpreterTypes, H: Host + ?Sized>(
     popn_top!([], top, interpreter);
     let offset = as_usize_or_fail!(interpreter, top);
     resize_memory!(interpreter, offset, 32);
-    *top = U256::try_from_be_slice(interpreter.memory.slice_len(offset, 32).as_ref()).unwrap()
+    *top = interpreter.memory.get_word(offset).into();
 }
 
 pub fn mstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::VERYLOW);
     popn!([offset, value], interpreter);
     let offset = as_usize_or_fail!(interpreter, offset);
     resize_memory!(interpreter, offset, 32);
-    interpreter.memory.set(offset, &value.to_be_bytes::<32>());
+    let word = value.to_be_bytes::<32>();
+    interpreter.memory.set(offset, &word);
+    host.trace_memory_write(offset, &word);
 }
 
 pub fn mstore8<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::VERYLOW);
     popn!([offset, value], interpreter);
     let offset = as_usize_or_fail!(interpreter, offset);
     resize_memory!(interpreter, offset, 1);
-    interpreter.memory.set(offset, &[value.byte(0)]);
+    let byte = [value.byte(0)];
+    interpreter.memory.set(offset, &byte);
+    host.trace_memory_write(offset, &byte);
 }
 
 pub fn msize<WIRE: InterpreterTypes, H: Host + ?Sized>(
@@ -51,7 +55,7 @@ pub fn msize<WIRE: InterpreterTypes, H: Host + ?Sized>(
 // EIP-5656: MCOPY - Memory copying instruction
 pub fn mcopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     check!(interpreter, CANCUN);
     popn!([dst, src, len], interpreter);
@@ -70,4 +74,114 @@ pub fn mcopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     resize_memory!(interpreter, max(dst, src), len);
     // Copy memory in place
     interpreter.memory.copy(dst, src, len);
+    host.trace_memory_write(dst, interpreter.memory.slice_len(dst, len).as_ref());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run, TestHost},
+        InstructionResult,
+    };
+    use bytecode::opcode::*;
+    use primitives::{Bytes, U256};
+    use std::vec;
+
+    #[test]
+    fn mstore_traces_memory_write() {
+        let mut host = TestHost::default().with_memory_write_tracking();
+        // MSTORE(0x20, 0xAABB) followed by MSTORE8(0x01, 0xCC).
+        let code = [
+            PUSH2, 0xAA, 0xBB, PUSH1, 0x20, MSTORE, PUSH1, 0xCC, PUSH1, 0x01, MSTORE8,
+        ];
+        let interp = run(&code, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+
+        let mut word = [0u8; 32];
+        word[30..].copy_from_slice(&[0xAA, 0xBB]);
+        assert_eq!(
+            host.memory_writes.unwrap(),
+            vec![
+                (0x20, Bytes::copy_from_slice(&word)),
+                (0x01, Bytes::from_static(&[0xCC]))
+            ]
+        );
+    }
+
+    #[test]
+    fn mstore8_writes_single_byte() {
+        let mut host = TestHost::default();
+        let interp = run(&[PUSH2, 0xAA, 0xBB, PUSH1, 0x00, MSTORE8, MSIZE], &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::from(32)]);
+
+        let mut word = [0u8; 32];
+        word[0] = 0xBB;
+        assert_eq!(interp.memory.borrow().slice_range(0..32), &word);
+        // PUSH2, PUSH1, MSTORE8 with one word of memory expansion and MSIZE.
+        assert_eq!(interp.control.gas.spent(), 3 + 3 + 3 + 3 + 2);
+    }
+
+    #[test]
+    fn mcopy_overlapping() {
+        let mut host = TestHost::default();
+        // Memory bytes 30 and 31 are `0x01 0x02`, then copied one byte forward.
+        let code = [
+            PUSH2, 0x01, 0x02, PUSH1, 0x00, MSTORE, PUSH1, 0x02, PUSH1, 30, PUSH1, 31, MCOPY,
+        ];
+        let interp = run(&code, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.memory.borrow().len(), 64);
+        assert_eq!(
+            interp.memory.borrow().slice_range(29..34),
+            &[0x00, 0x01, 0x01, 0x02, 0x00]
+        );
+        // Pushes, `MSTORE` and `MCOPY` of one word, each with one word of expansion.
+        assert_eq!(interp.control.gas.spent(), 5 * 3 + 3 + 3 + 6 + 3);
+
+        // Copied one byte backward.
+        let code = [
+            PUSH2, 0x01, 0x02, PUSH1, 0x00, MSTORE, PUSH1, 0x02, PUSH1, 30, PUSH1, 29, MCOPY,
+        ];
+        let interp = run(&code, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            interp.memory.borrow().slice_range(28..32),
+            &[0x00, 0x01, 0x02, 0x02]
+        );
+    }
+
+    #[test]
+    fn mload() {
+        let mut host = TestHost::default();
+        let interp = run(&[PUSH1, 0x00, MLOAD], &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::ZERO]);
+        assert_eq!(interp.memory.borrow().len(), 32);
+
+        let code = [PUSH2, 0xAA, 0xBB, PUSH1, 0x20, MSTORE, PUSH1, 0x20, MLOAD];
+        let interp = run(&code, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::from(0xAABB)]);
+
+        // Offset that doesn't fit in `usize`.
+        let mut code = vec![PUSH32];
+        code.extend([0xFF; 32]);
+        code.push(MLOAD);
+        let interp = run(&code, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::InvalidOperandOOG
+        );
+
+        // `usize::MAX` offset can't be paid for.
+        let mut code = vec![PUSH8];
+        code.extend([0xFF; 8]);
+        code.push(MLOAD);
+        let interp = run(&code, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::OutOfGas
+        );
+    }
 }
@@ -3,10 +3,11 @@ use crate::{
     instructions::utility::cast_slice_to_u256,
     interpreter::Interpreter,
     interpreter_types::{
-        Immediates, InterpreterTypes, Jumps, LoopControl, RuntimeFlag, StackTrait,
+        Immediates, InterpreterTypes, Jumps, LegacyBytecode, LoopControl, RuntimeFlag, StackTrait,
     },
     Host,
 };
+use context_interface::Cfg;
 use primitives::U256;
 
 pub fn pop<WIRE: InterpreterTypes, H: Host + ?Sized>(
@@ -30,11 +31,24 @@ pub fn push0<WIRE: InterpreterTypes, H: Host + ?Sized>(
     push!(interpreter, U256::ZERO);
 }
 
+/// Pushes `N` immediate bytes onto the stack.
+///
+/// Immediates past the end of legacy bytecode read as zeros from the analysis padding,
+/// unless strict push is enabled in [`Cfg`] in which case execution halts.
 pub fn push<const N: usize, WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     gas!(interpreter, gas::VERYLOW);
+    if !interpreter.runtime_flag.is_eof()
+        && interpreter.bytecode.pc() + N > interpreter.bytecode.bytecode_len()
+        && host.cfg().is_strict_push_enabled()
+    {
+        interpreter
+            .control
+            .set_instruction_result(crate::InstructionResult::OutOfOffset);
+        return;
+    }
     // TODO : Check performance degradation.
     push!(interpreter, U256::ZERO);
     popn_top!([], top, interpreter);
@@ -77,11 +91,14 @@ pub fn dupn<WIRE: InterpreterTypes, H: Host + ?Sized>(
 ) {
     require_eof!(interpreter);
     gas!(interpreter, gas::VERYLOW);
-    let imm = interpreter.bytecode.read_u8();
-    if !interpreter.stack.dup(imm as usize + 1) {
-        interpreter
-            .control
-            .set_instruction_result(crate::InstructionResult::StackOverflow);
+    let n = interpreter.bytecode.read_u8() as usize + 1;
+    if !interpreter.stack.dup(n) {
+        let result = if interpreter.stack.len() < n {
+            crate::InstructionResult::StackUnderflow
+        } else {
+            crate::InstructionResult::StackOverflow
+        };
+        interpreter.control.set_instruction_result(result);
     }
     interpreter.bytecode.relative_jump(1);
 }
@@ -118,6 +135,105 @@ pub fn exchange<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter.bytecode.relative_jump(1);
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{eof, non_returning, run, run_eof, run_with_spec, TestHost},
+        interpreter::EthInterpreter,
+        InstructionResult, Interpreter,
+    };
+    use bytecode::opcode::{DUPN, EXCHANGE, PUSH0, PUSH1, PUSH4, STOP, SWAPN};
+    use primitives::{Bytes, U256};
+    use specification::hardfork::SpecId;
+    use std::vec::Vec;
+
+    /// Runs `op` in EOF code after pushing the values `1..=21`.
+    fn run_eof_deep_stack(op: [u8; 2]) -> Interpreter<EthInterpreter> {
+        let mut code: Vec<u8> = (1..=21).flat_map(|i| [PUSH1, i]).collect();
+        code.extend(op);
+        code.push(STOP);
+        run_eof(eof(&[(non_returning(22), &code)], Bytes::new()))
+    }
+
+    #[test]
+    fn push0() {
+        let mut host = TestHost::default();
+        let interp = run(&[PUSH0], &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::ZERO]);
+        assert_eq!(interp.control.gas.spent(), 2);
+
+        let interp = run_with_spec(&[PUSH0], SpecId::MERGE, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::NotActivated
+        );
+        assert!(interp.stack.data().is_empty());
+    }
+
+    #[test]
+    fn dupn_deep() {
+        let interp = run_eof_deep_stack([DUPN, 20]);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.len(), 22);
+        assert_eq!(interp.stack.peek(0), Ok(U256::from(1)));
+        assert_eq!(interp.stack.peek(21), Ok(U256::from(1)));
+
+        // Only 21 items deep.
+        let interp = run_eof_deep_stack([DUPN, 21]);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::StackUnderflow
+        );
+        assert_eq!(interp.stack.len(), 21);
+    }
+
+    #[test]
+    fn swapn_deep() {
+        let interp = run_eof_deep_stack([SWAPN, 19]);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.len(), 21);
+        assert_eq!(interp.stack.peek(0), Ok(U256::from(1)));
+        assert_eq!(interp.stack.peek(20), Ok(U256::from(21)));
+        assert_eq!(interp.stack.peek(1), Ok(U256::from(20)));
+    }
+
+    #[test]
+    fn exchange_deep() {
+        // Exchanges the 3rd and 6th items from the top.
+        let interp = run_eof_deep_stack([EXCHANGE, 0x12]);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.peek(2), Ok(U256::from(16)));
+        assert_eq!(interp.stack.peek(5), Ok(U256::from(19)));
+        assert_eq!(interp.stack.peek(0), Ok(U256::from(21)));
+    }
+
+    #[test]
+    fn truncated_push_zero_fills() {
+        let mut host = TestHost::default();
+        let interp = run(&[PUSH4, 0xAA, 0xBB], &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::from(0xAABB0000u32)]);
+    }
+
+    #[test]
+    fn truncated_push_strict() {
+        let mut host = TestHost::default();
+        host.cfg.strict_push = true;
+        let interp = run(&[PUSH4, 0xAA, 0xBB], &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::OutOfOffset
+        );
+        assert!(interp.stack.data().is_empty());
+
+        // Complete immediates are not affected.
+        let interp = run(&[PUSH4, 0xAA, 0xBB, 0xCC, 0xDD], &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &vec![U256::from(0xAABBCCDDu32)]);
+    }
+}
+
 // TODO : Tests
 /*
 #[cfg(test)]
@@ -59,7 +59,7 @@ pub fn codesize<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
 pub fn codecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     popn!([memory_offset, code_offset, len], interpreter);
     let len = as_usize_or_fail!(interpreter, len);
@@ -68,12 +68,18 @@ pub fn codecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     };
     let code_offset = as_usize_saturated!(code_offset);
 
-    // Note: This can't panic because we resized memory to fit.
-    interpreter.memory.set_data(
+    let code = interpreter.bytecode.bytecode_slice();
+    let code = code.get(code_offset..).unwrap_or_default();
+    if let Err(result) = interpreter
+        .memory
+        .copy_from_slice_checked(memory_offset, code, len)
+    {
+        interpreter.control.set_instruction_result(result);
+        return;
+    }
+    host.trace_memory_write(
         memory_offset,
-        code_offset,
-        len,
-        interpreter.bytecode.bytecode_slice(),
+        interpreter.memory.slice_len(memory_offset, len).as_ref(),
     );
 }
 
@@ -118,7 +124,7 @@ pub fn callvalue<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
 pub fn calldatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     popn!([memory_offset, data_offset, len], interpreter);
     let len = as_usize_or_fail!(interpreter, len);
@@ -127,10 +133,19 @@ pub fn calldatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     };
 
     let data_offset = as_usize_saturated!(data_offset);
-    // Note: This can't panic because we resized memory to fit.
-    interpreter
+    let data = interpreter.input.input();
+    let data = data.get(data_offset..).unwrap_or_default();
+    if let Err(result) = interpreter
         .memory
-        .set_data(memory_offset, data_offset, len, interpreter.input.input());
+        .copy_from_slice_checked(memory_offset, data, len)
+    {
+        interpreter.control.set_instruction_result(result);
+        return;
+    }
+    host.trace_memory_write(
+        memory_offset,
+        interpreter.memory.slice_len(memory_offset, len).as_ref(),
+    );
 }
 
 /// EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
@@ -149,7 +164,7 @@ pub fn returndatasize<WIRE: InterpreterTypes, H: Host + ?Sized>(
 /// EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
 pub fn returndatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     check!(interpreter, BYZANTIUM);
     popn!([memory_offset, offset, len], interpreter);
@@ -171,12 +186,18 @@ pub fn returndatacopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
         return;
     };
 
-    // Note: This can't panic because we resized memory to fit.
-    interpreter.memory.set_data(
+    let data = interpreter.return_data.buffer();
+    let data = data.get(data_offset..).unwrap_or_default();
+    if let Err(result) = interpreter
+        .memory
+        .copy_from_slice_checked(memory_offset, data, len)
+    {
+        interpreter.control.set_instruction_result(result);
+        return;
+    }
+    host.trace_memory_write(
         memory_offset,
-        data_offset,
-        len,
-        interpreter.return_data.buffer(),
+        interpreter.memory.slice_len(memory_offset, len).as_ref(),
     );
 }
 
@@ -234,6 +255,39 @@ pub fn memory_resize(
     Some(memory_offset)
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run, TestHost},
+        InstructionResult,
+    };
+    use bytecode::opcode::*;
+    use primitives::{b256, U256};
+
+    #[test]
+    fn keccak256_empty_input() {
+        let empty_hash = b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+
+        let interp = run(
+            &[PUSH1, 0x00, PUSH1, 0x00, KECCAK256],
+            &mut TestHost::default(),
+        );
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from_be_bytes(empty_hash.0)]);
+        assert_eq!(interp.memory.borrow().len(), 0);
+        assert_eq!(interp.control.gas.spent(), 3 + 3 + 30);
+
+        // Offset is ignored for empty input, memory must not be expanded.
+        let mut code = vec![PUSH1, 0x00, PUSH32];
+        code.extend([0xff; 32]);
+        code.push(KECCAK256);
+        let interp = run(&code, &mut TestHost::default());
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from_be_bytes(empty_hash.0)]);
+        assert_eq!(interp.memory.borrow().len(), 0);
+    }
+}
+
 // TODO : Tests
 /*
 #[cfg(test)]
@@ -0,0 +1,119 @@
+//! Fixtures shared by the instruction and interpreter tests.
+
+use crate::{
+    interpreter::EthInterpreter, table::make_instruction_table, DummyHost, Host, InputsImpl,
+    Interpreter, SharedMemory,
+};
+use bytecode::{
+    eof::{EofBody, TypesSection},
+    Bytecode, Eof,
+};
+use context::{BlockEnv, CfgEnv, TxEnv};
+use primitives::Bytes;
+use specification::hardfork::SpecId;
+use std::{cell::RefCell, rc::Rc, sync::Arc, vec::Vec};
+
+pub(crate) type TestHost = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+/// Interpreter for `bytecode` with default inputs.
+pub(crate) fn new_interpreter(
+    bytecode: Bytecode,
+    spec_id: SpecId,
+    is_static: bool,
+    gas_limit: u64,
+) -> Interpreter<EthInterpreter> {
+    Interpreter::<EthInterpreter>::new(
+        Rc::new(RefCell::new(SharedMemory::new())),
+        bytecode,
+        InputsImpl::default(),
+        is_static,
+        false,
+        spec_id,
+        gas_limit,
+    )
+}
+
+/// Interpreter for legacy `code` with the latest spec.
+pub(crate) fn interpreter(code: &[u8], gas_limit: u64) -> Interpreter<EthInterpreter> {
+    new_interpreter(legacy(code), SpecId::LATEST, false, gas_limit)
+}
+
+/// Runs `bytecode` with 100_000 gas until it stops and returns the interpreter.
+pub(crate) fn run_bytecode<H: Host>(
+    bytecode: Bytecode,
+    spec_id: SpecId,
+    is_static: bool,
+    host: &mut H,
+) -> Interpreter<EthInterpreter> {
+    let mut interp = new_interpreter(bytecode, spec_id, is_static, 100_000);
+    let table = make_instruction_table::<EthInterpreter, H>();
+    let _ = interp.run(&table, host);
+    interp
+}
+
+/// Runs legacy `code` with the latest spec.
+pub(crate) fn run<H: Host>(code: &[u8], host: &mut H) -> Interpreter<EthInterpreter> {
+    run_with_spec(code, SpecId::LATEST, host)
+}
+
+/// Runs legacy `code` with `spec_id`.
+pub(crate) fn run_with_spec<H: Host>(
+    code: &[u8],
+    spec_id: SpecId,
+    host: &mut H,
+) -> Interpreter<EthInterpreter> {
+    run_bytecode(legacy(code), spec_id, false, host)
+}
+
+/// Runs legacy `code` with the latest spec in a static call.
+pub(crate) fn run_static<H: Host>(code: &[u8], host: &mut H) -> Interpreter<EthInterpreter> {
+    run_bytecode(legacy(code), SpecId::LATEST, true, host)
+}
+
+/// Runs `eof` with the Osaka spec and a default host.
+pub(crate) fn run_eof(eof: Arc<Eof>) -> Interpreter<EthInterpreter> {
+    run_bytecode(
+        Bytecode::Eof(eof),
+        SpecId::OSAKA,
+        false,
+        &mut TestHost::default(),
+    )
+}
+
+/// Analyzed legacy bytecode of `code`.
+pub(crate) fn legacy(code: &[u8]) -> Bytecode {
+    Bytecode::new_legacy(Bytes::copy_from_slice(code))
+}
+
+/// Types of a non-returning code section.
+pub(crate) fn non_returning(max_stack_size: u16) -> TypesSection {
+    TypesSection {
+        inputs: 0,
+        outputs: 0x80,
+        max_stack_size,
+    }
+}
+
+/// Builds an EOF container from `(types, code)` sections and a data section.
+pub(crate) fn eof(sections: &[(TypesSection, &[u8])], data: Bytes) -> Arc<Eof> {
+    let code_section = sections
+        .iter()
+        .scan(0, |end, (_, code)| {
+            *end += code.len();
+            Some(*end)
+        })
+        .collect();
+    let body = EofBody {
+        types_section: sections.iter().map(|(types, _)| *types).collect(),
+        code_section,
+        code: sections
+            .iter()
+            .flat_map(|(_, code)| *code)
+            .copied()
+            .collect(),
+        container_section: Vec::new(),
+        data_section: data,
+        is_data_filled: true,
+    };
+    Arc::new(body.into_eof())
+}
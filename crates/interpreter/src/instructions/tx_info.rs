@@ -48,3 +48,78 @@ pub fn blob_hash<WIRE: InterpreterTypes, H: Host + ?Sized>(
         U256::ZERO
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        instructions::test_utils::{run_with_spec, TestHost},
+        Host, InstructionResult,
+    };
+    use bytecode::opcode::*;
+    use context_interface::TransactionType;
+    use primitives::{B256, U256};
+    use specification::hardfork::SpecId;
+    use std::vec;
+
+    #[test]
+    fn blob_hash() {
+        let hashes = [B256::repeat_byte(1), B256::repeat_byte(2)];
+        let mut host = TestHost::default();
+        host.tx.tx_type = TransactionType::Eip4844;
+        host.tx.blob_hashes = hashes.to_vec();
+
+        let code = [
+            PUSH1, 0x00, BLOBHASH, PUSH1, 0x01, BLOBHASH, PUSH1, 0x02, BLOBHASH,
+        ];
+        let interp = run_with_spec(&code, SpecId::CANCUN, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            interp.stack.data(),
+            &vec![
+                hashes[0].into(),
+                hashes[1].into(),
+                // Out of range index.
+                U256::ZERO,
+            ]
+        );
+        assert_eq!(interp.control.gas.spent(), 6 * 3);
+
+        let interp = run_with_spec(&code, SpecId::SHANGHAI, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::NotActivated
+        );
+    }
+
+    #[test]
+    fn blob_base_fee() {
+        let mut host = TestHost::default();
+        // No blob gas pricing in the block.
+        host.block.blob_excess_gas_and_price = None;
+        assert_eq!(host.blob_base_fee(), U256::ZERO);
+        let interp = run_with_spec(&[BLOBBASEFEE], SpecId::CANCUN, &mut host);
+        assert_eq!(interp.stack.data(), &vec![U256::ZERO]);
+
+        host.block.set_blob_excess_gas_and_price(10_000_000);
+        let blob_base_fee = host.blob_base_fee();
+        assert!(blob_base_fee > U256::from(1));
+
+        // `BLOBHASH` and `BLOBBASEFEE` together.
+        host.tx.tx_type = TransactionType::Eip4844;
+        host.tx.blob_hashes = vec![B256::repeat_byte(1)];
+        let code = [PUSH1, 0x00, BLOBHASH, BLOBBASEFEE];
+        let interp = run_with_spec(&code, SpecId::CANCUN, &mut host);
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            interp.stack.data(),
+            &vec![B256::repeat_byte(1).into(), blob_base_fee]
+        );
+        assert_eq!(interp.control.gas.spent(), 3 + 3 + 2);
+
+        let interp = run_with_spec(&[BLOBBASEFEE], SpecId::SHANGHAI, &mut host);
+        assert_eq!(
+            interp.control.instruction_result,
+            InstructionResult::NotActivated
+        );
+    }
+}
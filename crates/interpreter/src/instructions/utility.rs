@@ -15,7 +15,7 @@ pub fn cast_slice_to_u256(slice: &[u8], dest: &mut U256) {
     }
     assert!(slice.len() <= 32, "slice too long");
 
-    let n_words = (slice.len() + 31) / 32;
+    let n_words = slice.len().div_ceil(32);
 
     // SAFETY: Length checked above.
     unsafe {
@@ -57,7 +57,7 @@ pub fn cast_slice_to_u256(slice: &[u8], dest: &mut U256) {
             i += 1;
         }
 
-        debug_assert_eq!((i + 3) / 4, n_words, "wrote too much");
+        debug_assert_eq!(i.div_ceil(4), n_words, "wrote too much");
 
         // Zero out upper bytes of last word
         let m = i % 4; // 32 / 8
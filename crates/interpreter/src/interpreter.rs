@@ -1,3 +1,5 @@
+mod builder;
+mod config;
 pub mod ext_bytecode;
 mod input;
 mod loop_control;
@@ -10,22 +12,28 @@ mod stack;
 mod subroutine_stack;
 
 use crate::{
-    interpreter_types::*, table::CustomInstruction, Gas, Host, Instruction, InstructionResult,
-    InterpreterAction,
+    interpreter_types::*, table::CustomInstruction, CallGasBreakdown, Gas, Host, Instruction,
+    InstructionResult, InterpreterAction,
 };
-use bytecode::Bytecode;
+use bytecode::{opcode::OpCode, Bytecode, Eof};
+use context_interface::Cfg;
 
-use core::cell::RefCell;
+pub use builder::{InterpreterBuildError, InterpreterBuilder};
+pub use config::InterpreterConfig;
+use core::{
+    cell::{Ref, RefCell},
+    num::NonZeroU64,
+};
 pub use ext_bytecode::ExtBytecode;
 pub use input::InputsImpl;
 use loop_control::LoopControl as LoopControlImpl;
 use primitives::Bytes;
 use return_data::ReturnDataImpl;
 pub use runtime_flags::RuntimeFlags;
-pub use shared_memory::{num_words, MemoryGetter, SharedMemory, EMPTY_SHARED_MEMORY};
+pub use shared_memory::{num_words, MemoryGetter, MemoryGrowth, SharedMemory, EMPTY_SHARED_MEMORY};
 use specification::hardfork::SpecId;
 pub use stack::{Stack, STACK_LIMIT};
-use std::rc::Rc;
+use std::{rc::Rc, sync::Arc};
 use subroutine_stack::SubRoutineImpl;
 
 #[derive(Debug, Clone)]
@@ -40,9 +48,20 @@ pub struct Interpreter<WIRE: InterpreterTypes> {
     pub control: WIRE::Control,
     pub runtime_flag: WIRE::RuntimeFlag,
     pub extend: WIRE::Extend,
+    /// Call depth of the frame this interpreter runs in.
+    depth: usize,
+    /// Gas breakdown of the last `CALL` like instruction, until the sub call returns.
+    pub(crate) call_gas: Option<CallGasBreakdown>,
 }
 
 impl<EXT: Default, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
+    /// Returns a builder for the interpreter.
+    ///
+    /// Prefer it over [`Interpreter::new`] when only some of the inputs are set.
+    pub fn builder() -> InterpreterBuilder<EXT, MG> {
+        InterpreterBuilder::default()
+    }
+
     /// Create new interpreter
     pub fn new(
         memory: Rc<RefCell<MG>>,
@@ -69,7 +88,158 @@ impl<EXT: Default, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
             control: LoopControlImpl::new(gas_limit),
             runtime_flag,
             extend: EXT::default(),
+            depth: 0,
+            call_gas: None,
+        }
+    }
+
+    /// Create new interpreter from an already decoded and validated EOF container.
+    ///
+    /// The container is not decoded or validated again, execution starts at the beginning
+    /// of code section zero.
+    pub fn new_eof(
+        memory: Rc<RefCell<MG>>,
+        eof: Arc<Eof>,
+        inputs: InputsImpl,
+        is_static: bool,
+        is_eof_init: bool,
+        spec_id: SpecId,
+        gas_limit: u64,
+    ) -> Self {
+        Self::new(
+            memory,
+            Bytecode::Eof(eof),
+            inputs,
+            is_static,
+            is_eof_init,
+            spec_id,
+            gas_limit,
+        )
+    }
+
+    /// Applies the stack limit of `config`.
+    ///
+    /// Memory is shared between calls, use [`InterpreterConfig::new_memory`] to create it.
+    pub fn with_config(mut self, config: &InterpreterConfig) -> Self {
+        self.stack = Stack::with_capacity(config.stack_limit, config.stack_limit);
+        self
+    }
+
+    /// Resets the interpreter to execute `bytecode` as if it was created with [`Interpreter::new`].
+    ///
+    /// Stack and sub routine stack keep their allocations. Memory is shared between
+    /// calls and is left as is, use [`SharedMemory::clear`] to reuse it for a new transaction.
+    pub fn reset(
+        &mut self,
+        bytecode: Bytecode,
+        inputs: InputsImpl,
+        is_static: bool,
+        is_eof_init: bool,
+        spec_id: SpecId,
+        gas_limit: u64,
+    ) {
+        self.runtime_flag = RuntimeFlags {
+            spec_id,
+            is_static,
+            is_eof: bytecode.is_eof(),
+            is_eof_init,
+        };
+        self.bytecode = ExtBytecode::new(bytecode);
+        self.stack.clear();
+        self.return_data = ReturnDataImpl::default();
+        self.input = inputs;
+        self.sub_routine.return_stack.clear();
+        self.sub_routine.current_code_idx = 0;
+        self.control = LoopControlImpl::new(gas_limit);
+        self.extend = EXT::default();
+        self.depth = 0;
+        self.call_gas = None;
+    }
+}
+
+impl<EXT, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
+    /// Returns `true` if both interpreters are in the same execution state.
+    ///
+    /// Compares the program counter, stack values, memory contents, spent gas and
+    /// instruction result. Memory is compared up to its length and stack by its values,
+    /// so differences in allocated capacity are ignored.
+    pub fn state_eq(&self, other: &Self) -> bool {
+        self.bytecode.pc() == other.bytecode.pc()
+            && self.stack.data() == other.stack.data()
+            && self.control.gas.spent() == other.control.gas.spent()
+            && self.control.instruction_result == other.control.instruction_result
+            && *self.memory.slice(0..self.memory.size())
+                == *other.memory.slice(0..other.memory.size())
+    }
+
+    /// Returns an independent copy of the interpreter.
+    ///
+    /// Unlike the memory of a sub call frame, the shared memory is deep cloned so the
+    /// fork and the original can be stepped separately without affecting each other.
+    pub fn fork(&self) -> Self
+    where
+        EXT: Clone,
+        MG: Clone,
+    {
+        Self {
+            bytecode: self.bytecode.clone(),
+            stack: self.stack.clone(),
+            return_data: self.return_data.clone(),
+            memory: Rc::new(RefCell::new(self.memory.borrow().clone())),
+            input: self.input.clone(),
+            sub_routine: self.sub_routine.clone(),
+            control: self.control.clone(),
+            runtime_flag: self.runtime_flag,
+            extend: self.extend.clone(),
+            depth: self.depth,
+            call_gas: self.call_gas,
+        }
+    }
+
+    /// Returns the jump target and condition of the `JUMPI` at the current instruction pointer.
+    ///
+    /// The condition is `true` if the jump would be taken. Targets that don't fit in
+    /// `usize` are saturated. Returns `None` if the current instruction is not a legacy
+    /// `JUMPI` or the stack holds fewer than two items.
+    pub fn peek_jumpi(&self) -> Option<(usize, bool)> {
+        if self.runtime_flag.is_eof() || self.current_opcode()? != OpCode::JUMPI {
+            return None;
+        }
+        let target = self.stack.peek(0).ok()?;
+        let condition = self.stack.peek(1).ok()?;
+        Some((
+            usize::try_from(target).unwrap_or(usize::MAX),
+            !condition.is_zero(),
+        ))
+    }
+
+    /// Returns the output of the `RETURN` or `REVERT` at the current instruction pointer
+    /// without copying it out of memory.
+    ///
+    /// Returns `None` if the current instruction is neither, the stack holds fewer than
+    /// two items or the range is not within the current memory. The owned output is
+    /// available in [`InterpreterResult::output`] once the instruction is executed.
+    pub fn return_value_ref(&self) -> Option<Ref<'_, [u8]>> {
+        let opcode = self.current_opcode()?;
+        if opcode != OpCode::RETURN && opcode != OpCode::REVERT {
+            return None;
         }
+        let offset = self.stack.peek(0).ok()?;
+        let len = usize::try_from(self.stack.peek(1).ok()?).ok()?;
+        // Offset is ignored if length is zero.
+        let offset = if len == 0 {
+            0
+        } else {
+            usize::try_from(offset).ok()?
+        };
+        let end = offset.checked_add(len)?;
+        let memory = self.memory.borrow();
+        if end > memory.memory().len() {
+            return None;
+        }
+        Some(Ref::map(memory, |memory| {
+            memory.memory().slice_range(offset..end)
+        }))
     }
 }
 
@@ -150,6 +320,73 @@ impl<IW: InterpreterTypes, H: Host> CustomInstruction for Instruction<IW, H> {
 }
 
 impl<IW: InterpreterTypes> Interpreter<IW> {
+    /// Sets the call depth of the frame this interpreter runs in.
+    ///
+    /// Defaults to `0`, the top-level call.
+    #[inline]
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the denominator of the quadratic part of the memory expansion cost.
+    ///
+    /// See [`Gas::set_memory_quad_coeff_div`].
+    #[inline]
+    pub fn with_memory_quad_coeff_div(mut self, quad_coeff_div: NonZeroU64) -> Self {
+        self.control.gas().set_memory_quad_coeff_div(quad_coeff_div);
+        self
+    }
+
+    /// Returns the call depth of the frame this interpreter runs in.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Takes the gas breakdown of the pending `CALL`, `CALLCODE`, `DELEGATECALL` or
+    /// `STATICCALL`.
+    ///
+    /// `returned` is not known to the interpreter and is left as zero, the handler sets it
+    /// when the sub call returns.
+    #[inline]
+    pub fn take_call_gas(&mut self) -> Option<CallGasBreakdown> {
+        self.call_gas.take()
+    }
+
+    /// Returns the opcode at the current instruction pointer.
+    ///
+    /// This is the opcode that will be executed by the next [`step`][Interpreter::step].
+    /// Returns `None` if the byte is not a valid opcode or if the instruction pointer
+    /// is past the end of the legacy bytecode.
+    #[inline]
+    pub fn current_opcode(&self) -> Option<OpCode> {
+        if !self.runtime_flag.is_eof() && self.bytecode.pc() >= self.bytecode.bytecode_len() {
+            return None;
+        }
+        OpCode::new(self.bytecode.opcode())
+    }
+
+    /// Returns the number of bytes occupied by the instruction at the current instruction
+    /// pointer, including its immediates.
+    ///
+    /// Legacy `PUSH` immediates truncated by the end of the bytecode are not counted.
+    /// Returns `None` if [`current_opcode`][Interpreter::current_opcode] is `None`.
+    #[inline]
+    pub fn current_opcode_size(&self) -> Option<usize> {
+        let opcode = self.current_opcode()?;
+        let mut size = 1 + opcode.info().immediate_size() as usize;
+        if self.runtime_flag.is_eof() {
+            if opcode == OpCode::RJUMPV {
+                // Jump table has `max_index + 1` relative offsets.
+                size += (self.bytecode.read_slice(2)[1] as usize + 1) * 2;
+            }
+        } else {
+            size = size.min(self.bytecode.bytecode_len() - self.bytecode.pc());
+        }
+        Some(size)
+    }
+
     /// Executes the instruction at the current instruction pointer.
     ///
     /// Internally it will increment instruction pointer by one.
@@ -187,6 +424,345 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
             self.step(instruction_table, host);
         }
 
+        self.take_action()
+    }
+
+    /// Executes the interpreter until it returns or stops, calling `hook` after each
+    /// instruction.
+    ///
+    /// The hook gets the executed opcode and the gas before it was executed. It can halt
+    /// the interpreter by setting the next action, or pause it by returning an outcome.
+    /// Bounded runs below are built on it; [`Interpreter::run`] keeps its own loop so the
+    /// hot path stays free of hooks.
+    #[inline]
+    fn run_with_hook<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        mut hook: impl FnMut(&mut Self, u8, &Gas) -> Option<RunOutcome>,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control
+            .set_next_action(InterpreterAction::None, InstructionResult::Continue);
+
+        while self.control.instruction_result().is_continue() {
+            let opcode = self.bytecode.opcode();
+            let gas = *self.control.gas();
+            self.step(instruction_table, host);
+            if let Some(outcome) = hook(self, opcode, &gas) {
+                return outcome;
+            }
+        }
+
+        RunOutcome::Done(self.take_action())
+    }
+
+    /// Same as [`Interpreter::run_with_hook`], but checks `should_pause` before each
+    /// instruction instead of after it.
+    #[inline]
+    fn run_with_pre_hook<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        mut should_pause: impl FnMut(&mut Self) -> Option<RunOutcome>,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control
+            .set_next_action(InterpreterAction::None, InstructionResult::Continue);
+        if let Some(outcome) = should_pause(self) {
+            return outcome;
+        }
+        self.run_with_hook(instruction_table, host, |interp, _, _| {
+            if !interp.control.instruction_result().is_continue() {
+                return None;
+            }
+            should_pause(interp)
+        })
+    }
+
+    /// Executes the interpreter until it returns, stops or executes `max_steps` instructions.
+    ///
+    /// Returns the next action and the number of instructions executed. When the limit is
+    /// reached before execution finishes, the interpreter halts with
+    /// [`InstructionResult::OutOfInstructions`]; a limit of zero halts before executing
+    /// anything. The limit applies to this call frame only, nested calls are run separately.
+    pub fn run_with_step_limit<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        max_steps: u64,
+    ) -> (InterpreterAction, u64)
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        if max_steps == 0 {
+            self.control.set_next_action(
+                InterpreterAction::None,
+                InstructionResult::OutOfInstructions,
+            );
+            return (self.take_action(), 0);
+        }
+
+        let mut steps = 0;
+        let outcome = self.run_with_hook(instruction_table, host, |interp, _, _| {
+            steps += 1;
+            if steps == max_steps && interp.control.instruction_result().is_continue() {
+                interp.control.set_next_action(
+                    InterpreterAction::None,
+                    InstructionResult::OutOfInstructions,
+                );
+            }
+            None
+        });
+        (outcome.into_action(), steps)
+    }
+
+    /// Executes the interpreter until it returns, stops or the spent gas reaches `gas_budget`.
+    ///
+    /// The budget is checked before every instruction: the interpreter pauses before an
+    /// instruction whose [static gas cost][crate::gas::static_gas_cost] would push
+    /// [`Gas::spent`] past `gas_budget`, and returns [`RunOutcome::Paused`]. Execution can
+    /// be resumed by calling this function again with a bigger budget or by calling
+    /// [`Interpreter::run`].
+    ///
+    /// Instructions with dynamic gas are only known to cross the budget after they are
+    /// executed, so they still run in full and the interpreter pauses after them.
+    pub fn run_to_gas<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        gas_budget: u64,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.run_with_pre_hook(instruction_table, host, |interp| {
+            let spent = interp.control.gas().spent();
+            let cost = crate::gas::static_gas_cost(
+                interp.bytecode.opcode(),
+                interp.runtime_flag.spec_id(),
+            )
+            .unwrap_or_default();
+            (spent >= gas_budget || spent + cost > gas_budget).then_some(RunOutcome::Paused)
+        })
+    }
+
+    /// Executes up to `n` instructions.
+    ///
+    /// Returns [`RunOutcome::Paused`] if all `n` instructions were executed without the
+    /// interpreter finishing. Execution can be resumed by calling this function again or
+    /// by calling [`Interpreter::run`].
+    pub fn step_n<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        n: usize,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        match self.run_with_step_limit(instruction_table, host, n as u64) {
+            (InterpreterAction::Return { result }, _)
+                if result.result == InstructionResult::OutOfInstructions =>
+            {
+                self.control
+                    .set_instruction_result(InstructionResult::Continue);
+                RunOutcome::Paused
+            }
+            (action, _) => RunOutcome::Done(action),
+        }
+    }
+
+    /// Executes the interpreter until it returns, stops or the `deadline` passes.
+    ///
+    /// The clock is read every `check_interval` instructions to amortize its cost,
+    /// so the deadline can be overshot by up to that many instructions. When the deadline
+    /// has passed [`RunOutcome::TimedOut`] is returned and execution can be resumed.
+    #[cfg(feature = "std")]
+    pub fn run_with_deadline<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        deadline: std::time::Instant,
+        check_interval: usize,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        let check_interval = check_interval.max(1);
+        let mut until_check = 0;
+        self.run_with_pre_hook(instruction_table, host, |_| {
+            if until_check == 0 {
+                if std::time::Instant::now() >= deadline {
+                    return Some(RunOutcome::TimedOut);
+                }
+                until_check = check_interval;
+            }
+            until_check -= 1;
+            None
+        })
+    }
+
+    /// Executes the interpreter until it returns, stops or the stack length reaches `depth`.
+    ///
+    /// The stack length is checked after each instruction, so a stack that is already at
+    /// `depth` when called does not pause. When paused [`RunOutcome::Paused`] is returned and
+    /// the stack can be inspected before execution is resumed.
+    pub fn run_until_stack_depth<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        depth: usize,
+    ) -> RunOutcome
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.run_with_hook(instruction_table, host, |interp, _, _| {
+            (interp.stack.len() == depth && interp.control.instruction_result().is_continue())
+                .then_some(RunOutcome::Paused)
+        })
+    }
+
+    /// Executes the interpreter until it returns or stops, charging overridden gas costs.
+    ///
+    /// When `overrides[opcode]` is `Some(cost)`, the base cost of the instruction is replaced
+    /// by `cost` while memory expansion is still charged on top, so it is meant for repricing
+    /// experiments. The overridden instruction is run with enough gas for its real cost, which
+    /// is then swapped for `cost`, so a cheaper override succeeds even when the real cost
+    /// could not be paid.
+    ///
+    /// Only instructions whose other costs come from memory expansion should be overridden:
+    /// account and storage access and copy charges are replaced together with the base cost,
+    /// and calls, creates and the `SSTORE` stipend check see the extra gas.
+    pub fn run_with_gas_overrides<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        overrides: &[Option<u64>; 256],
+    ) -> InterpreterAction
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control
+            .set_next_action(InterpreterAction::None, InstructionResult::Continue);
+
+        while self.control.instruction_result().is_continue() {
+            let Some(cost) = overrides[self.bytecode.opcode() as usize] else {
+                self.step(instruction_table, host);
+                continue;
+            };
+            let before = self.control.gas().report();
+            let headroom = u64::MAX - before.limit;
+            self.control.gas().add_headroom(headroom);
+            self.step(instruction_table, host);
+
+            let failed = self.control.instruction_result().is_error();
+            let gas = self.control.gas();
+            let mut in_budget = true;
+            if !failed {
+                let after = gas.report();
+                let memory_cost = after.memory - before.memory;
+                gas.erase_cost(after.spent - before.spent - memory_cost);
+                in_budget = gas.record_cost(cost);
+            }
+            in_budget &= gas.remove_headroom(headroom);
+            if !in_budget && !failed {
+                self.control
+                    .set_next_action(InterpreterAction::None, InstructionResult::OutOfGas);
+            }
+        }
+
+        self.take_action()
+    }
+
+    /// Executes the interpreter until it returns or stops, halting when a single
+    /// instruction spends more than `max_single_opcode_gas`.
+    ///
+    /// The limit catches pathological inputs such as huge copy lengths. Gas is measured
+    /// after the instruction is executed and exceeding the limit halts with
+    /// [`InstructionResult::OpcodeGasLimitOOG`]. [`Interpreter::run`] is unbounded.
+    ///
+    /// Instructions that start a sub call or create are not checked, as their charge
+    /// includes the gas forwarded to the new frame.
+    pub fn run_with_max_opcode_gas<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        max_single_opcode_gas: u64,
+    ) -> InterpreterAction
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.run_with_hook(instruction_table, host, |interp, _, before| {
+            if interp.control.instruction_result() != InstructionResult::CallOrCreate
+                && interp.control.gas().spent().saturating_sub(before.spent())
+                    > max_single_opcode_gas
+            {
+                interp.control.set_next_action(
+                    InterpreterAction::None,
+                    InstructionResult::OpcodeGasLimitOOG,
+                );
+            }
+            None
+        })
+        .into_action()
+    }
+
+    /// Executes the interpreter until it returns or stops, verifying gas accounting every
+    /// `check_interval` instructions.
+    ///
+    /// A shadow counter is kept next to [`Gas::spent`] without trusting the instructions'
+    /// own charges: instructions with a [static gas cost][crate::gas::static_gas_cost] add
+    /// exactly that cost, other instructions add what they spent but no less than their
+    /// memory expansion cost. Instructions that overcharge a static cost, or that give gas
+    /// back, make the counters drift. Instructions that halt with an error are not checked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shadow counter doesn't match the spent gas.
+    pub fn run_with_gas_check<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        check_interval: usize,
+    ) -> InterpreterAction
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        let check_interval = check_interval.max(1);
+        let mut shadow_spent = self.control.gas().spent();
+        let mut steps = 0;
+        self.run_with_hook(instruction_table, host, |interp, opcode, before| {
+            let spec_id = interp.runtime_flag.spec_id();
+            let failed = interp.control.instruction_result().is_error();
+            let gas = interp.control.gas();
+            let spent = gas.spent().saturating_sub(before.spent());
+            let memory = gas.report().memory.saturating_sub(before.report().memory);
+            shadow_spent += match crate::gas::static_gas_cost(opcode, spec_id) {
+                _ if failed => spent,
+                Some(cost) => cost,
+                None => spent.max(memory),
+            };
+
+            steps += 1;
+            if !failed && steps % check_interval == 0 {
+                assert_eq!(
+                    shadow_spent,
+                    gas.spent(),
+                    "gas accounting drifted after {steps} instructions"
+                );
+            }
+            None
+        })
+        .into_action()
+    }
+
+    /// Takes the next action after the main loop has finished.
+    fn take_action(&mut self) -> InterpreterAction {
         // Return next action if it is some.
         let action = self.control.take_next_action();
         if action.is_some() {
@@ -204,6 +780,171 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
     }
 }
 
+/// Executes `init` code as a contract creation and returns the deployed runtime code.
+///
+/// Init code is run as legacy bytecode in a fresh [`Interpreter`] with spec and code size
+/// limit taken from the host [`Cfg`], the same way `CREATE` runs it. Returned code is
+/// checked against the EIP-170 code size limit.
+///
+/// Sub calls and creates are not supported and result in
+/// [`InstructionResult::FatalExternalError`]. Reverts and halts are returned as errors.
+pub fn execute_init_code<FN, H: Host>(
+    init: Bytes,
+    gas_limit: u64,
+    instruction_table: &[FN; 256],
+    host: &mut H,
+) -> Result<Bytes, InstructionResult>
+where
+    FN: CustomInstruction<Wire = EthInterpreter, Host = H>,
+{
+    let spec_id: SpecId = host.cfg().spec().into();
+    let mut interpreter = Interpreter::<EthInterpreter>::new(
+        Rc::new(RefCell::new(SharedMemory::new())),
+        Bytecode::new_legacy(init),
+        InputsImpl::default(),
+        false,
+        false,
+        spec_id,
+        gas_limit,
+    );
+
+    let InterpreterAction::Return { result } = interpreter.run(instruction_table, host) else {
+        return Err(InstructionResult::FatalExternalError);
+    };
+    if !result.is_ok() {
+        return Err(result.result);
+    }
+
+    // EIP-170: Contract code size limit
+    if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON)
+        && result.output.len() > host.cfg().max_code_size()
+    {
+        return Err(InstructionResult::CreateContractSizeLimit);
+    }
+    Ok(result.output)
+}
+
+/// Returns `true` if the result of executing `bytecode` depends on the gas limit.
+///
+/// Bytecode is run in a fresh [`Interpreter`] once with `low_gas_limit` and once with
+/// `high_gas_limit`, and the instruction results and outputs are compared. Running out
+/// of gas with the lower limit is reported as gas sensitive, so it should be high
+/// enough for the expected execution. State changes of each run are reverted.
+///
+/// Sub calls and creates are not supported and are compared as
+/// [`InstructionResult::FatalExternalError`].
+pub fn is_gas_sensitive<FN, H: Host>(
+    bytecode: Bytecode,
+    inputs: InputsImpl,
+    low_gas_limit: u64,
+    high_gas_limit: u64,
+    instruction_table: &[FN; 256],
+    host: &mut H,
+) -> bool
+where
+    FN: CustomInstruction<Wire = EthInterpreter, Host = H>,
+{
+    let mut run =
+        |gas_limit| run_standalone(&bytecode, &inputs, gas_limit, instruction_table, host);
+    run(low_gas_limit) != run(high_gas_limit)
+}
+
+/// Returns the smallest gas limit with which `bytecode` executes successfully.
+///
+/// The limit is binary searched between zero and `max_gas_limit`, assuming that execution
+/// succeeding with a limit also succeeds with any higher limit. Returns `None` if execution
+/// doesn't succeed with `max_gas_limit`, for example because it reverts or runs out of gas.
+/// State changes of each run are reverted, so every run starts from the same state.
+///
+/// Sub calls and creates are not supported and are treated as failed execution.
+pub fn minimum_gas_limit<FN, H: Host>(
+    bytecode: Bytecode,
+    inputs: InputsImpl,
+    max_gas_limit: u64,
+    instruction_table: &[FN; 256],
+    host: &mut H,
+) -> Option<u64>
+where
+    FN: CustomInstruction<Wire = EthInterpreter, Host = H>,
+{
+    let mut succeeds = |gas_limit| {
+        run_standalone(&bytecode, &inputs, gas_limit, instruction_table, host)
+            .0
+            .is_ok()
+    };
+    if !succeeds(max_gas_limit) {
+        return None;
+    }
+    // Execution fails with `low` and succeeds with `high`.
+    let (mut low, mut high) = (0, max_gas_limit);
+    if succeeds(low) {
+        return Some(low);
+    }
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if succeeds(mid) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    Some(high)
+}
+
+/// Runs `bytecode` in a fresh [`Interpreter`] and returns the instruction result and output.
+///
+/// State changes are reverted through [`Host::checkpoint_revert`] after the run, so runs
+/// don't observe each other. Sub calls and creates result in
+/// [`InstructionResult::FatalExternalError`].
+fn run_standalone<FN, H: Host>(
+    bytecode: &Bytecode,
+    inputs: &InputsImpl,
+    gas_limit: u64,
+    instruction_table: &[FN; 256],
+    host: &mut H,
+) -> (InstructionResult, Bytes)
+where
+    FN: CustomInstruction<Wire = EthInterpreter, Host = H>,
+{
+    let mut interpreter = Interpreter::<EthInterpreter>::new(
+        Rc::new(RefCell::new(SharedMemory::new())),
+        bytecode.clone(),
+        inputs.clone(),
+        false,
+        false,
+        host.cfg().spec().into(),
+        gas_limit,
+    );
+    let checkpoint = host.checkpoint();
+    let result = match interpreter.run(instruction_table, host) {
+        InterpreterAction::Return { result } => (result.result, result.output),
+        _ => (InstructionResult::FatalExternalError, Bytes::new()),
+    };
+    host.checkpoint_revert(checkpoint);
+    result
+}
+
+/// The outcome of a bounded interpreter run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The interpreter was paused and can be resumed.
+    Paused,
+    /// The deadline has passed, the interpreter can be resumed.
+    TimedOut,
+    /// The interpreter finished execution with the given action.
+    Done(InterpreterAction),
+}
+
+impl RunOutcome {
+    /// Returns the action of a run that can't pause.
+    fn into_action(self) -> InterpreterAction {
+        match self {
+            Self::Done(action) => action,
+            Self::Paused | Self::TimedOut => unreachable!("run was not expected to pause"),
+        }
+    }
+}
+
 /// The result of an interpreter operation.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
@@ -287,10 +1028,640 @@ mod tests {
     // }
 
     use super::*;
-    use bytecode::Bytecode;
-    use primitives::{Address, Bytes, U256};
+    use crate::{
+        instructions::test_utils::{eof, interpreter, non_returning, TestHost},
+        table::make_instruction_table,
+        EmptyHost,
+    };
+    use bytecode::{eof::TypesSection, opcode::*, Bytecode};
+    use context::{BlockEnv, CfgEnv, TxEnv};
+    use primitives::{Bytes, B256, U256};
     use specification::hardfork::SpecId;
-    use std::{cell::RefCell, rc::Rc};
+    use std::{cell::RefCell, rc::Rc, vec, vec::Vec};
+
+    #[test]
+    fn fork_is_independent() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let code = [
+            PUSH1, 0x01, PUSH1, 0x00, MSTORE, PUSH1, 0x02, PUSH1, 0x00, MSTORE, STOP,
+        ];
+        let mut interp = interpreter(&code, 100_000);
+        let _ = interp.run_with_step_limit(&table, &mut host, 3);
+
+        let mut fork = interp.fork();
+        assert!(fork.state_eq(&interp));
+
+        let result = fork.run(&table, &mut host).into_result_return().unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(fork.memory.get_word(0), B256::with_last_byte(2));
+
+        // The original is still positioned after the first MSTORE.
+        assert_eq!(interp.bytecode.pc(), 5);
+        assert!(interp.stack.is_empty());
+        assert_eq!(interp.memory.get_word(0), B256::with_last_byte(1));
+        assert!(interp.control.gas().spent() < fork.control.gas().spent());
+        assert!(!fork.state_eq(&interp));
+    }
+
+    #[test]
+    fn reset() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut interp = interpreter(&[PUSH1, 0x01, PUSH1, 0x02, ADD, STOP], 100_000);
+        let _ = interp.run(&table, &mut host);
+        let stack_ptr = interp.stack.data().as_ptr();
+
+        let code = [PUSH1, 0x03, STOP];
+        interp.reset(
+            Bytecode::new_raw(Bytes::copy_from_slice(&code)),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::LATEST,
+            1_000,
+        );
+        assert!(interp.state_eq(&interpreter(&code, 1_000)));
+        assert_eq!(interp.control.gas().limit(), 1_000);
+        // Stack allocation is reused.
+        assert_eq!(interp.stack.data().as_ptr(), stack_ptr);
+
+        let result = interp.run(&table, &mut host).into_result_return().unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from(3)]);
+    }
+
+    #[test]
+    fn new_eof() {
+        let mut interp = Interpreter::<EthInterpreter>::new_eof(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            eof(
+                &[
+                    (non_returning(0), &[CALLF, 0x00, 0x01, STOP]),
+                    (TypesSection::default(), &[RETF]),
+                ],
+                Bytes::new(),
+            ),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::OSAKA,
+            100_000,
+        );
+        assert!(interp.runtime_flag.is_eof());
+        assert_eq!(interp.bytecode.pc(), 0);
+
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let result = interp
+            .run(&table, &mut TestHost::default())
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        // `CALLF` and `RETF`.
+        assert_eq!(result.gas.spent(), 5 + 3);
+    }
+
+    #[test]
+    fn step_n() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut interp = interpreter(&[PUSH1, 0x01, PUSH1, 0x02, ADD, STOP], 100_000);
+
+        assert_eq!(interp.step_n(&table, &mut host, 2), RunOutcome::Paused);
+        assert_eq!(interp.bytecode.pc(), 4);
+        assert_eq!(interp.stack.len(), 2);
+
+        let RunOutcome::Done(action) = interp.step_n(&table, &mut host, 10) else {
+            panic!("expected interpreter to finish");
+        };
+        let result = action.into_result_return().unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from(3)]);
+    }
+
+    #[test]
+    fn peek_jumpi() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut interp = interpreter(
+            &[PUSH1, 0x01, PUSH1, 0x07, JUMPI, STOP, STOP, JUMPDEST, STOP],
+            100_000,
+        );
+        assert_eq!(interp.peek_jumpi(), None);
+
+        let _ = interp.run_with_step_limit(&table, &mut host, 2);
+        let stack = interp.stack.data().clone();
+        assert_eq!(interp.peek_jumpi(), Some((7, true)));
+        assert_eq!(interp.stack.data(), &stack);
+        assert_eq!(interp.bytecode.pc(), 4);
+    }
+
+    #[test]
+    fn gas_sensitive() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let inputs = InputsImpl::default();
+        // Reverts if less than 100_000 gas is left.
+        let branching = Bytecode::new_raw(Bytes::from([
+            PUSH3, 0x01, 0x86, 0xA0, GAS, LT, PUSH1, 0x0E, JUMPI, PUSH1, 0x00, PUSH1, 0x00, RETURN,
+            JUMPDEST, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+        ]));
+        assert!(is_gas_sensitive(
+            branching,
+            inputs.clone(),
+            50_000,
+            1_000_000,
+            &table,
+            &mut host
+        ));
+
+        let plain = Bytecode::new_raw(Bytes::from([
+            PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ]));
+        assert!(!is_gas_sensitive(
+            plain, inputs, 50_000, 1_000_000, &table, &mut host
+        ));
+    }
+
+    #[test]
+    fn minimum_gas_limit() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let inputs = InputsImpl::default();
+        // Four `PUSH1`, `MSTORE` and one word of memory expansion.
+        let code = Bytecode::new_raw(Bytes::from([
+            PUSH1, 0x01, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ]));
+        assert_eq!(
+            super::minimum_gas_limit(code.clone(), inputs.clone(), 1_000_000, &table, &mut host),
+            Some(3 * 4 + 3 + 3)
+        );
+        assert_eq!(
+            super::minimum_gas_limit(code, inputs.clone(), 17, &table, &mut host),
+            None
+        );
+        assert_eq!(
+            super::minimum_gas_limit(
+                Bytecode::new_raw(Bytes::from([STOP])),
+                inputs.clone(),
+                1_000_000,
+                &table,
+                &mut host
+            ),
+            Some(0)
+        );
+
+        let reverting = Bytecode::new_raw(Bytes::from([PUSH1, 0x00, PUSH1, 0x00, REVERT]));
+        assert_eq!(
+            super::minimum_gas_limit(reverting, inputs, 1_000_000, &table, &mut host),
+            None
+        );
+    }
+
+    #[test]
+    fn current_opcode_size() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut interp = interpreter(&[PUSH2, 0x01, 0x02, PUSH1, 0x03, ADD, PUSH2, 0x01], 100_000);
+        assert_eq!(interp.current_opcode_size(), Some(3));
+        let _ = interp.step_n(&table, &mut host, 2);
+        assert_eq!(interp.current_opcode_size(), Some(1));
+        let _ = interp.step_n(&table, &mut host, 1);
+        // Truncated immediate.
+        assert_eq!(interp.current_opcode_size(), Some(2));
+        let _ = interp.step_n(&table, &mut host, 1);
+        assert_eq!(interp.current_opcode_size(), None);
+
+        let mut interp = Interpreter::<EthInterpreter>::new_eof(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            eof(
+                &[(
+                    non_returning(1),
+                    &[
+                        RJUMP, 0x00, 0x00, PUSH0, RJUMPV, 0x01, 0x00, 0x00, 0x00, 0x00, STOP,
+                    ],
+                )],
+                Bytes::new(),
+            ),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::OSAKA,
+            100_000,
+        );
+        assert_eq!(interp.current_opcode_size(), Some(3));
+        let _ = interp.step_n(&table, &mut host, 2);
+        // `RJUMPV` with a two entry jump table.
+        assert_eq!(interp.current_opcode_size(), Some(6));
+    }
+
+    #[test]
+    fn return_value_ref() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let code = [
+            PUSH1, 0x2a, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let mut interp = interpreter(&code, 100_000);
+        assert!(interp.return_value_ref().is_none());
+
+        let _ = interp.run_with_step_limit(&table, &mut host, 5);
+        assert_eq!(
+            interp.return_value_ref().as_deref(),
+            Some(&B256::with_last_byte(0x2a)[..])
+        );
+
+        // Range past the end of memory is not expanded.
+        let mut interp = interpreter(&[PUSH1, 0x20, PUSH1, 0x00, REVERT], 100_000);
+        let _ = interp.run_with_step_limit(&table, &mut host, 2);
+        assert!(interp.return_value_ref().is_none());
+
+        // Offset is ignored for empty output.
+        let mut code = vec![PUSH1, 0x00, PUSH32];
+        code.extend([0xff; 32]);
+        code.push(RETURN);
+        let mut interp = interpreter(&code, 100_000);
+        let _ = interp.run_with_step_limit(&table, &mut host, 2);
+        assert_eq!(interp.return_value_ref().as_deref(), Some(&[][..]));
+
+        let result = interp.run(&table, &mut host).into_result_return().unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn run_to_gas_pauses_at_budget() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // Ten `PUSH1 0x01` instructions, 3 gas each.
+        let code = [PUSH1, 0x01].repeat(10);
+        let mut interp = interpreter(&code, 30);
+
+        assert_eq!(interp.run_to_gas(&table, &mut host, 15), RunOutcome::Paused);
+        assert_eq!(interp.bytecode.pc(), 10);
+        assert_eq!(interp.stack.len(), 5);
+        assert_eq!(interp.control.gas().remaining(), 15);
+
+        let result = interp.run(&table, &mut host).into_result_return().unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(result.gas.remaining(), 0);
+
+        // The fifth `PUSH1` would spend 15 gas, so it is not executed.
+        let mut interp = interpreter(&code, 30);
+        assert_eq!(interp.run_to_gas(&table, &mut host, 14), RunOutcome::Paused);
+        assert_eq!(interp.bytecode.pc(), 8);
+        assert_eq!(interp.stack.len(), 4);
+        assert_eq!(interp.control.gas().spent(), 12);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn run_with_deadline_times_out() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // Infinite loop: `JUMPDEST PUSH1 0x00 JUMP`.
+        let mut interp = interpreter(&[JUMPDEST, PUSH1, 0x00, JUMP], u64::MAX);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(10);
+        assert_eq!(
+            interp.run_with_deadline(&table, &mut host, deadline, 1000),
+            RunOutcome::TimedOut
+        );
+        assert!(interp.control.instruction_result().is_continue());
+        assert!(interp.control.gas().spent() > 0);
+    }
+
+    #[test]
+    fn run_with_max_opcode_gas_halts() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // `CALLDATACOPY` of 64 KiB to memory offset zero.
+        let code = [
+            PUSH3,
+            0x01,
+            0x00,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            CALLDATACOPY,
+            STOP,
+        ];
+
+        let mut interp = interpreter(&code, 1_000_000);
+        let result = interp
+            .run_with_max_opcode_gas(&table, &mut host, 1_000)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::OpcodeGasLimitOOG);
+        assert_eq!(interp.bytecode.pc(), 9);
+
+        let mut interp = interpreter(&code, 1_000_000);
+        let result = interp
+            .run_with_max_opcode_gas(&table, &mut host, u64::MAX)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert!(result.gas.spent() > 1_000);
+
+        // Gas forwarded to a sub call doesn't count towards the limit.
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, GAS, CALL,
+        ];
+        let action =
+            interpreter(&code, 1_000_000).run_with_max_opcode_gas(&table, &mut host, 10_000);
+        assert!(matches!(action, InterpreterAction::NewFrame(_)));
+    }
+
+    #[test]
+    fn run_until_stack_depth_pauses() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let code = [
+            PUSH1, 0x01, PUSH1, 0x02, PUSH1, 0x03, PUSH1, 0x04, ADD, STOP,
+        ];
+        let mut interp = interpreter(&code, 100);
+
+        assert_eq!(
+            interp.run_until_stack_depth(&table, &mut host, 3),
+            RunOutcome::Paused
+        );
+        assert_eq!(interp.bytecode.pc(), 6);
+        assert_eq!(
+            interp.stack.data(),
+            &[U256::from(1), U256::from(2), U256::from(3)]
+        );
+
+        // `ADD` brings the stack back to depth 3.
+        assert_eq!(
+            interp.run_until_stack_depth(&table, &mut host, 3),
+            RunOutcome::Paused
+        );
+        assert_eq!(
+            interp.stack.data(),
+            &[U256::from(1), U256::from(2), U256::from(7)]
+        );
+
+        let outcome = interp.run_until_stack_depth(&table, &mut host, 3);
+        assert!(matches!(outcome, RunOutcome::Done(_)));
+        assert_eq!(interp.control.instruction_result, InstructionResult::Stop);
+    }
+
+    #[test]
+    fn run_with_gas_overrides() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let code = [PUSH1, 0x00, SLOAD, STOP];
+
+        let mut overrides = [None; 256];
+        let result = interpreter(&code, 100_000)
+            .run_with_gas_overrides(&table, &mut TestHost::default(), &overrides)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        // Cold `SLOAD`.
+        assert_eq!(result.gas.spent(), 3 + 2100);
+
+        overrides[SLOAD as usize] = Some(5000);
+        let result = interpreter(&code, 100_000)
+            .run_with_gas_overrides(&table, &mut TestHost::default(), &overrides)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(result.gas.spent(), 3 + 5000);
+
+        // Memory expansion is charged on top of the overridden base cost.
+        let code = [PUSH1, 0x01, PUSH1, 0x40, MSTORE, STOP];
+        let mut overrides = [None; 256];
+        overrides[MSTORE as usize] = Some(1);
+        let result = interpreter(&code, 100_000)
+            .run_with_gas_overrides(&table, &mut TestHost::default(), &overrides)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        // Three words of memory cost 9 gas.
+        assert_eq!(result.gas.spent(), 3 + 3 + 1 + 9);
+
+        // Override that can't be paid halts.
+        let code = [PUSH1, 0x00, SLOAD, STOP];
+        overrides[SLOAD as usize] = Some(5000);
+        let result = interpreter(&code, 3000)
+            .run_with_gas_overrides(&table, &mut TestHost::default(), &overrides)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+
+        // Override cheaper than the real cost succeeds with less gas than the real cost.
+        overrides[SLOAD as usize] = Some(100);
+        let result = interpreter(&code, 2000)
+            .run_with_gas_overrides(&table, &mut TestHost::default(), &overrides)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(result.gas.spent(), 3 + 100);
+        assert_eq!(result.gas.limit(), 2000);
+    }
+
+    #[test]
+    fn run_with_step_limit() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // `PUSH1 0x01` three times followed by `STOP`.
+        let code = [PUSH1, 0x01].repeat(3);
+
+        let (action, steps) = interpreter(&code, 100).run_with_step_limit(&table, &mut host, 4);
+        assert_eq!(
+            action.into_result_return().unwrap().result,
+            InstructionResult::Stop
+        );
+        assert_eq!(steps, 4);
+
+        let mut interp = interpreter(&code, 100);
+        let (action, steps) = interp.run_with_step_limit(&table, &mut host, 2);
+        assert_eq!(
+            action.into_result_return().unwrap().result,
+            InstructionResult::OutOfInstructions
+        );
+        assert_eq!(steps, 2);
+        assert_eq!(interp.stack.len(), 2);
+
+        let mut interp = interpreter(&code, 100);
+        let (_, steps) = interp.run_with_step_limit(&table, &mut host, 0);
+        assert_eq!(steps, 0);
+        assert_eq!(interp.bytecode.pc(), 0);
+    }
+
+    #[test]
+    fn run_with_gas_check() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        // Stores 64 words, expanding memory on every store.
+        let code: Vec<u8> = (0..64u16)
+            .flat_map(|i| {
+                [
+                    PUSH1,
+                    i as u8,
+                    PUSH2,
+                    (i >> 3) as u8,
+                    (i << 5) as u8,
+                    MSTORE,
+                ]
+            })
+            .collect();
+        let result = interpreter(&code, 100_000)
+            .run_with_gas_check(&table, &mut TestHost::default(), 7)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Stop);
+
+        // Running out of gas halts without a drift.
+        let result = interpreter(&code, 500)
+            .run_with_gas_check(&table, &mut TestHost::default(), 1)
+            .into_result_return()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+    }
+
+    #[test]
+    #[should_panic(expected = "gas accounting drifted")]
+    fn run_with_gas_check_detects_drift() {
+        fn refunding_jumpdest(interpreter: &mut Interpreter<EthInterpreter>, _: &mut TestHost) {
+            interpreter.control.gas().erase_cost(1);
+        }
+
+        let mut table = make_instruction_table::<EthInterpreter, TestHost>();
+        table[JUMPDEST as usize] = refunding_jumpdest;
+        let _ = interpreter(&[PUSH1, 0x00, JUMPDEST, STOP], 100_000).run_with_gas_check(
+            &table,
+            &mut TestHost::default(),
+            1,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "gas accounting drifted")]
+    fn run_with_gas_check_detects_overcharge() {
+        fn overcharging_add(interpreter: &mut Interpreter<EthInterpreter>, host: &mut TestHost) {
+            crate::instructions::arithmetic::add(interpreter, host);
+            assert!(interpreter.control.gas().record_cost(1));
+        }
+
+        let mut table = make_instruction_table::<EthInterpreter, TestHost>();
+        table[ADD as usize] = overcharging_add;
+        let _ = interpreter(&[PUSH1, 0x01, PUSH1, 0x02, ADD, STOP], 100_000).run_with_gas_check(
+            &table,
+            &mut TestHost::default(),
+            1,
+        );
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(interpreter(&[STOP], 0).depth(), 0);
+        assert_eq!(interpreter(&[STOP], 0).with_depth(3).depth(), 3);
+    }
+
+    #[test]
+    fn empty_host() {
+        let table = make_instruction_table::<EthInterpreter, EmptyHost<BlockEnv, TxEnv, CfgEnv>>();
+        let mut host = EmptyHost::default();
+
+        let mut interp = interpreter(&[PUSH1, 0x02, PUSH1, 0x03, MUL, STOP], 100);
+        let _ = interp.run(&table, &mut host);
+        assert_eq!(interp.control.instruction_result(), InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::from(6)]);
+
+        let mut interp = interpreter(&[PUSH1, 0x00, SLOAD], 10_000);
+        let _ = interp.run(&table, &mut host);
+        assert_eq!(
+            interp.control.instruction_result(),
+            InstructionResult::FatalExternalError
+        );
+    }
+
+    #[test]
+    fn current_opcode() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut interp = interpreter(&[PUSH1, 0x01, 0x0C], 100);
+
+        assert_eq!(interp.current_opcode(), Some(OpCode::PUSH1));
+        interp.step(&table, &mut host);
+        assert_eq!(interp.current_opcode(), None);
+
+        let mut interp = interpreter(&[PUSH1, 0x01], 100);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.bytecode.pc(), 2);
+        assert_eq!(interp.current_opcode(), None);
+    }
+
+    #[test]
+    fn execute_init_code_returns_runtime() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // Store `0xAABBCC` in memory and return it as runtime code.
+        let init = Bytes::from_static(&[
+            PUSH3, 0xAA, 0xBB, 0xCC, PUSH1, 0x00, MSTORE, PUSH1, 0x03, PUSH1, 0x1D, RETURN,
+        ]);
+
+        let runtime = execute_init_code(init, 100_000, &table, &mut host).unwrap();
+        assert_eq!(runtime, Bytes::from_static(&[0xAA, 0xBB, 0xCC]));
+
+        let init = Bytes::from_static(&[PUSH1, 0x00, PUSH1, 0x00, REVERT]);
+        assert_eq!(
+            execute_init_code(init, 100_000, &table, &mut host),
+            Err(InstructionResult::Revert)
+        );
+    }
+
+    #[test]
+    fn execute_init_code_ef_prefix() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        // EOF and EIP-7702 prefixes are not decoded, `0xEF` halts as legacy code.
+        for init in [
+            Bytes::from_static(&[0xEF, 0x00, 0x01, 0x01]),
+            Bytes::from_static(&[0xEF, 0x01, 0x00]),
+        ] {
+            assert_eq!(
+                execute_init_code(init, 100_000, &table, &mut host),
+                Err(InstructionResult::OpcodeNotFound)
+            );
+        }
+    }
+
+    #[test]
+    fn state_eq() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let code = [PUSH1, 0x2A, PUSH1, 0x00, MSTORE, PUSH1, 0x01];
+
+        let mut first = interpreter(&code, 100);
+        let mut second = interpreter(&code, 100);
+        let _ = first.run(&table, &mut host);
+        let _ = second.run(&table, &mut host);
+        assert!(first.state_eq(&second));
+
+        let mut other = interpreter(&[PUSH1, 0x2A, PUSH1, 0x00, MSTORE, PUSH1, 0x02], 100);
+        let _ = other.run(&table, &mut host);
+        assert!(!first.state_eq(&other));
+    }
+
+    #[test]
+    fn stack_max_depth() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let mut code = [PUSH1, 0x01].repeat(5);
+        code.extend([POP; 3]);
+        let mut interp = interpreter(&code, 100);
+
+        let _ = interp.run(&table, &mut host);
+        assert_eq!(interp.control.instruction_result(), InstructionResult::Stop);
+        assert_eq!(interp.stack.len(), 2);
+        assert_eq!(interp.stack.max_depth(), 5);
+
+        interp.stack.clear();
+        assert_eq!(interp.stack.max_depth(), 0);
+    }
 
     #[test]
     #[cfg(feature = "serde")]
@@ -299,12 +1670,7 @@ mod tests {
         let interpreter = Interpreter::<EthInterpreter>::new(
             Rc::new(RefCell::new(SharedMemory::new())),
             bytecode,
-            InputsImpl {
-                target_address: Address::ZERO,
-                caller_address: Address::ZERO,
-                input: Bytes::default(),
-                call_value: U256::ZERO,
-            },
+            InputsImpl::default(),
             false,
             false,
             SpecId::LATEST,
@@ -321,4 +1687,31 @@ mod tests {
             "Program counter should be preserved"
         );
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_resume() {
+        let table = make_instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+        let code = [
+            PUSH1, 0x01, PUSH1, 0x00, MSTORE, PUSH1, 0x02, PUSH1, 0x20, MSTORE, PUSH1, 0x40, PUSH1,
+            0x00, RETURN,
+        ];
+        let expected = interpreter(&code, 100_000)
+            .run(&table, &mut host)
+            .into_result_return()
+            .unwrap();
+
+        let mut interp = interpreter(&code, 100_000);
+        let _ = interp.run_with_step_limit(&table, &mut host, 7);
+        assert_eq!(interp.stack.len(), 1);
+
+        let mut check = |mut resumed: Interpreter<EthInterpreter>| {
+            assert!(resumed.state_eq(&interp));
+            let result = resumed.run(&table, &mut host).into_result_return().unwrap();
+            assert_eq!(result, expected);
+        };
+        check(bincode::deserialize(&bincode::serialize(&interp).unwrap()).unwrap());
+        check(serde_json::from_str(&serde_json::to_string(&interp).unwrap()).unwrap());
+    }
 }
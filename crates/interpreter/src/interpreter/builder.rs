@@ -0,0 +1,213 @@
+use super::{
+    EthInterpreter, InputsImpl, Interpreter, InterpreterConfig, MemoryGetter, SharedMemory,
+};
+use bytecode::Bytecode;
+use core::{cell::RefCell, fmt, marker::PhantomData};
+use primitives::{Address, Bytes, U256};
+use specification::hardfork::SpecId;
+use std::rc::Rc;
+
+/// Builder for [`Interpreter`], created with [`Interpreter::builder`].
+///
+/// Bytecode and gas limit are required. Memory defaults to a new `MG`, inputs to zero
+/// addresses, empty call data and zero value, the spec to [`SpecId::LATEST`] and the config
+/// to [`InterpreterConfig::default`].
+#[derive(Debug)]
+pub struct InterpreterBuilder<EXT = (), MG = SharedMemory> {
+    memory: Option<Rc<RefCell<MG>>>,
+    bytecode: Option<Bytecode>,
+    inputs: InputsImpl,
+    is_static: bool,
+    is_eof_init: bool,
+    spec_id: SpecId,
+    gas_limit: Option<u64>,
+    config: InterpreterConfig,
+    _phantom: PhantomData<fn() -> EXT>,
+}
+
+impl<EXT, MG> Default for InterpreterBuilder<EXT, MG> {
+    fn default() -> Self {
+        Self {
+            memory: None,
+            bytecode: None,
+            inputs: InputsImpl::default(),
+            is_static: false,
+            is_eof_init: false,
+            spec_id: SpecId::LATEST,
+            gas_limit: None,
+            config: InterpreterConfig::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<EXT, MG> InterpreterBuilder<EXT, MG> {
+    /// Sets the memory, shared with other interpreters of the same transaction.
+    pub fn memory(mut self, memory: Rc<RefCell<MG>>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Sets the bytecode to execute.
+    pub fn code(mut self, bytecode: Bytecode) -> Self {
+        self.bytecode = Some(bytecode);
+        self
+    }
+
+    /// Sets the call data.
+    pub fn call_data(mut self, input: Bytes) -> Self {
+        self.inputs.input = input;
+        self
+    }
+
+    /// Sets the address of the executing contract.
+    pub fn address(mut self, address: Address) -> Self {
+        self.inputs.target_address = address;
+        self
+    }
+
+    /// Sets the caller address.
+    pub fn caller(mut self, caller: Address) -> Self {
+        self.inputs.caller_address = caller;
+        self
+    }
+
+    /// Sets the call value.
+    pub fn value(mut self, value: U256) -> Self {
+        self.inputs.call_value = value;
+        self
+    }
+
+    /// Sets all inputs at once, overriding call data, address, caller and value.
+    pub fn inputs(mut self, inputs: InputsImpl) -> Self {
+        self.inputs = inputs;
+        self
+    }
+
+    /// Sets whether the call is static.
+    pub fn is_static(mut self, is_static: bool) -> Self {
+        self.is_static = is_static;
+        self
+    }
+
+    /// Sets whether the bytecode is EOF initcode.
+    pub fn is_eof_init(mut self, is_eof_init: bool) -> Self {
+        self.is_eof_init = is_eof_init;
+        self
+    }
+
+    /// Sets the spec.
+    pub fn spec_id(mut self, spec_id: SpecId) -> Self {
+        self.spec_id = spec_id;
+        self
+    }
+
+    /// Sets the gas limit.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the config.
+    pub fn config(mut self, config: InterpreterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Builds the interpreter.
+    ///
+    /// Returns an error if the bytecode or the gas limit is not set.
+    pub fn build(self) -> Result<Interpreter<EthInterpreter<EXT, MG>>, InterpreterBuildError>
+    where
+        EXT: Default,
+        MG: MemoryGetter + Default,
+    {
+        let bytecode = self.bytecode.ok_or(InterpreterBuildError::MissingCode)?;
+        let gas_limit = self
+            .gas_limit
+            .ok_or(InterpreterBuildError::MissingGasLimit)?;
+        Ok(Interpreter::new(
+            self.memory.unwrap_or_default(),
+            bytecode,
+            self.inputs,
+            self.is_static,
+            self.is_eof_init,
+            self.spec_id,
+            gas_limit,
+        )
+        .with_config(&self.config))
+    }
+}
+
+/// Required field missing in [`InterpreterBuilder::build`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InterpreterBuildError {
+    /// Bytecode was not set.
+    MissingCode,
+    /// Gas limit was not set.
+    MissingGasLimit,
+}
+
+impl fmt::Display for InterpreterBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::MissingCode => "missing bytecode",
+            Self::MissingGasLimit => "missing gas limit",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for InterpreterBuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interpreter_types::InputsTrait, STACK_LIMIT};
+    use bytecode::opcode::STOP;
+    use primitives::address;
+
+    #[test]
+    fn build() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let interp: Interpreter<EthInterpreter> = Interpreter::builder()
+            .code(Bytecode::new_legacy([STOP].into()))
+            .caller(caller)
+            .value(U256::from(7))
+            .gas_limit(100)
+            .build()
+            .unwrap();
+        assert_eq!(interp.input.caller_address(), caller);
+        assert_eq!(interp.input.call_value(), U256::from(7));
+        assert_eq!(interp.control.gas.limit(), 100);
+        assert_eq!(interp.runtime_flag.spec_id, SpecId::LATEST);
+        assert_eq!(interp.stack.limit(), STACK_LIMIT);
+
+        let config = InterpreterConfig {
+            stack_limit: 16,
+            ..Default::default()
+        };
+        let interp: Interpreter<EthInterpreter> = Interpreter::builder()
+            .code(Bytecode::new_legacy([STOP].into()))
+            .gas_limit(100)
+            .config(config)
+            .build()
+            .unwrap();
+        assert_eq!(interp.stack.limit(), 16);
+
+        assert_eq!(
+            InterpreterBuilder::<(), SharedMemory>::default()
+                .gas_limit(100)
+                .build()
+                .err(),
+            Some(InterpreterBuildError::MissingCode)
+        );
+        assert_eq!(
+            InterpreterBuilder::<(), SharedMemory>::default()
+                .code(Bytecode::new_legacy([STOP].into()))
+                .build()
+                .err(),
+            Some(InterpreterBuildError::MissingGasLimit)
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use super::{SharedMemory, STACK_LIMIT};
+use core::cell::RefCell;
+use std::rc::Rc;
+
+/// Limits and initial allocations of an [`Interpreter`][super::Interpreter].
+///
+/// Defaults conform to the EVM specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InterpreterConfig {
+    /// Maximum number of words on the stack, [STACK_LIMIT] by default.
+    pub stack_limit: usize,
+    /// Initial capacity in bytes of the memory created with [`InterpreterConfig::new_memory`].
+    ///
+    /// Defaults to 4KiB, same as [`SharedMemory::new`].
+    pub memory_capacity: usize,
+}
+
+impl Default for InterpreterConfig {
+    fn default() -> Self {
+        Self {
+            stack_limit: STACK_LIMIT,
+            memory_capacity: 4 * 1024,
+        }
+    }
+}
+
+impl InterpreterConfig {
+    /// Creates memory with the configured initial capacity, to be shared between calls.
+    pub fn new_memory(&self) -> Rc<RefCell<SharedMemory>> {
+        Rc::new(RefCell::new(SharedMemory::with_capacity(
+            self.memory_capacity,
+        )))
+    }
+}
@@ -1,21 +1,32 @@
 use bytecode::{
     eof::TypesSection,
     utils::{read_i16, read_u16},
-    Bytecode,
+    Bytecode, JumpTable,
 };
 use primitives::Bytes;
 
 use super::{EofCodeInfo, EofContainer, EofData, Immediates, Jumps, LegacyBytecode};
 
+mod disassemble;
 #[cfg(feature = "serde")]
 mod serde;
 
+pub use disassemble::{DisassembledOp, DisassembledOpcode};
+
 #[derive(Debug)]
 pub struct ExtBytecode {
     base: Bytecode,
     instruction_pointer: *const u8,
 }
 
+impl Clone for ExtBytecode {
+    fn clone(&self) -> Self {
+        let mut bytecode = Self::new(self.base.clone());
+        bytecode.absolute_jump(self.pc());
+        bytecode
+    }
+}
+
 impl AsRef<Bytecode> for ExtBytecode {
     fn as_ref(&self) -> &Bytecode {
         &self.base
@@ -31,6 +42,16 @@ impl ExtBytecode {
             instruction_pointer,
         }
     }
+
+    /// Returns the jump table of legacy bytecode, `None` for EOF and EIP-7702 bytecode.
+    ///
+    /// Legacy bytecode is analyzed once when the [`Bytecode`] is created and the table is
+    /// reference counted, so this never reanalyzes the code and bytecode created from a
+    /// clone of the same [`Bytecode`] shares the table.
+    #[inline]
+    pub fn preanalyze(&self) -> Option<&JumpTable> {
+        self.base.legacy_jump_table()
+    }
 }
 
 impl Jumps for ExtBytecode {
@@ -79,7 +100,7 @@ impl Immediates for ExtBytecode {
 
     #[inline]
     fn read_i8(&self) -> i8 {
-        unsafe { core::mem::transmute(*self.instruction_pointer) }
+        unsafe { *self.instruction_pointer as i8 }
     }
 
     #[inline]
@@ -164,3 +185,27 @@ impl LegacyBytecode for ExtBytecode {
         self.base.original_byte_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode::{JUMPDEST, STOP};
+    use std::sync::Arc;
+
+    #[test]
+    fn preanalyze_shares_jump_table() {
+        let base = Bytecode::new_legacy(Bytes::from([JUMPDEST, STOP]));
+        let bytecode = ExtBytecode::new(base.clone());
+        let table = bytecode.preanalyze().unwrap();
+        assert!(table.is_valid(0));
+        assert!(Arc::ptr_eq(
+            &table.0,
+            &ExtBytecode::new(base.clone()).preanalyze().unwrap().0
+        ));
+        assert!(Arc::ptr_eq(&table.0, &base.legacy_jump_table().unwrap().0));
+
+        assert!(ExtBytecode::new(Bytecode::new_eip7702(Default::default()))
+            .preanalyze()
+            .is_none());
+    }
+}
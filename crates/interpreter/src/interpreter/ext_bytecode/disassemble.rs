@@ -0,0 +1,182 @@
+use super::ExtBytecode;
+use bytecode::{opcode::OpCode, Bytecode};
+use core::fmt;
+use primitives::{hex, Bytes};
+use std::vec::Vec;
+
+/// Opcode of a [DisassembledOp].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisassembledOpcode {
+    /// Known opcode.
+    Known(OpCode),
+    /// Byte that is not a known opcode.
+    Unknown(u8),
+}
+
+impl fmt::Display for DisassembledOpcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Known(opcode) => fmt::Display::fmt(opcode, f),
+            Self::Unknown(byte) => write!(f, "UNKNOWN(0x{byte:02X})"),
+        }
+    }
+}
+
+/// Single instruction of the disassembled bytecode.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DisassembledOp {
+    /// Index of the EOF code section, zero for legacy bytecode.
+    pub section: usize,
+    /// Offset of the instruction from the start of its code section.
+    pub offset: usize,
+    /// Opcode of the instruction.
+    pub opcode: DisassembledOpcode,
+    /// Immediate bytes of the instruction.
+    ///
+    /// Legacy bytecode only has `PUSH` immediates, truncated `PUSH` immediates at the
+    /// end of the bytecode are shorter than the `PUSH` size. In EOF all instructions
+    /// with immediates have them set, `RJUMPV` includes its jump table.
+    pub immediate: Option<Bytes>,
+}
+
+impl fmt::Display for DisassembledOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04x}: {}", self.offset, self.opcode)?;
+        if let Some(immediate) = &self.immediate {
+            write!(f, " 0x{}", hex::encode(immediate))?;
+        }
+        Ok(())
+    }
+}
+
+impl ExtBytecode {
+    /// Disassembles the bytecode.
+    ///
+    /// EOF code sections are disassembled independently, offsets are relative to the start
+    /// of the section. Invalid bytes are emitted as [`DisassembledOpcode::Unknown`] and
+    /// immediates that run past the end of the code are truncated, so any input can be
+    /// disassembled. EIP-7702 bytecode has no instructions.
+    pub fn disassemble(&self) -> Vec<DisassembledOp> {
+        let mut ops = Vec::new();
+        match &self.base {
+            Bytecode::LegacyAnalyzed(analyzed) => {
+                disassemble_code(analyzed.original_byte_slice(), 0, false, &mut ops)
+            }
+            Bytecode::Eof(eof) => {
+                for section in 0..eof.body.code_section.len() {
+                    if let Some(code) = eof.body.code(section) {
+                        disassemble_code(&code, section, true, &mut ops);
+                    }
+                }
+            }
+            Bytecode::Eip7702(_) => {}
+        }
+        ops
+    }
+}
+
+fn disassemble_code(code: &[u8], section: usize, is_eof: bool, ops: &mut Vec<DisassembledOp>) {
+    let mut offset = 0;
+    while offset < code.len() {
+        let byte = code[offset];
+        let (opcode, mut immediate_size) = match OpCode::new(byte) {
+            Some(opcode) if is_eof || opcode.is_push() => (
+                DisassembledOpcode::Known(opcode),
+                opcode.info().immediate_size() as usize,
+            ),
+            Some(opcode) => (DisassembledOpcode::Known(opcode), 0),
+            None => (DisassembledOpcode::Unknown(byte), 0),
+        };
+        if is_eof && opcode == DisassembledOpcode::Known(OpCode::RJUMPV) {
+            if let Some(&max_index) = code.get(offset + 1) {
+                // Jump table has `max_index + 1` relative offsets.
+                immediate_size += (max_index as usize + 1) * 2;
+            }
+        }
+
+        let start = offset + 1;
+        let end = (start + immediate_size).min(code.len());
+        ops.push(DisassembledOp {
+            section,
+            offset,
+            opcode,
+            immediate: (immediate_size != 0).then(|| Bytes::copy_from_slice(&code[start..end])),
+        });
+        offset = start + immediate_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_utils::{eof, non_returning};
+    use bytecode::{
+        eof::TypesSection,
+        opcode::{ADD, PUSH1, PUSH2, RETF, RJUMP, STOP},
+    };
+    use primitives::bytes;
+    use std::string::ToString;
+
+    #[test]
+    fn disassemble_legacy() {
+        let code = Bytes::from([PUSH2, 0x01, 0x02, ADD, 0x0C, PUSH2, 0xFF]);
+        let ops = ExtBytecode::new(Bytecode::new_legacy(code)).disassemble();
+        assert_eq!(
+            ops,
+            [
+                DisassembledOp {
+                    section: 0,
+                    offset: 0,
+                    opcode: DisassembledOpcode::Known(OpCode::PUSH2),
+                    immediate: Some(bytes!("0102")),
+                },
+                DisassembledOp {
+                    section: 0,
+                    offset: 3,
+                    opcode: DisassembledOpcode::Known(OpCode::ADD),
+                    immediate: None,
+                },
+                DisassembledOp {
+                    section: 0,
+                    offset: 4,
+                    opcode: DisassembledOpcode::Unknown(0x0C),
+                    immediate: None,
+                },
+                // Truncated immediate.
+                DisassembledOp {
+                    section: 0,
+                    offset: 5,
+                    opcode: DisassembledOpcode::Known(OpCode::PUSH2),
+                    immediate: Some(bytes!("ff")),
+                },
+            ]
+        );
+        assert_eq!(ops[0].to_string(), "0000: PUSH2 0x0102");
+        assert_eq!(ops[2].to_string(), "0004: UNKNOWN(0x0C)");
+    }
+
+    #[test]
+    fn disassemble_eof_sections() {
+        let bytecode = ExtBytecode::new(Bytecode::Eof(eof(
+            &[
+                (non_returning(1), &[PUSH1, 0x2a, RJUMP, 0x00, 0x00, STOP]),
+                (TypesSection::default(), &[RETF]),
+            ],
+            Bytes::new(),
+        )));
+        let ops: Vec<_> = bytecode
+            .disassemble()
+            .into_iter()
+            .map(|op| (op.section, op.to_string()))
+            .collect();
+        assert_eq!(
+            ops,
+            [
+                (0, "0000: PUSH1 0x2a".to_string()),
+                (0, "0002: RJUMP 0x0000".to_string()),
+                (0, "0005: STOP".to_string()),
+                (1, "0000: RETF".to_string()),
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use primitives::{Address, Bytes, U256};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InputsImpl {
     pub target_address: Address,
@@ -3,6 +3,7 @@ use crate::{Gas, InstructionResult, InterpreterAction};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LoopControl {
     /// The execution control flag.
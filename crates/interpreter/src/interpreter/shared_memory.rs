@@ -2,18 +2,20 @@ use core::{
     cell::{Ref, RefCell},
     cmp::min,
     fmt,
+    hash::{Hash, Hasher},
     ops::{Deref, Range},
 };
 use primitives::{hex, B256, U256};
 use std::{rc::Rc, vec::Vec};
 
 use super::MemoryTrait;
+use crate::InstructionResult;
 
 /// A sequential memory shared between calls, which uses
 /// a `Vec` for internal representation.
 /// A [SharedMemory] instance should always be obtained using
 /// the `new` static method to ensure memory safety.
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SharedMemory {
     /// The underlying buffer.
@@ -26,6 +28,22 @@ pub struct SharedMemory {
     /// Memory limit. See [`Cfg`](context_interface::Cfg).
     #[cfg(feature = "memory_limit")]
     memory_limit: u64,
+    /// Strategy used to grow the buffer.
+    growth: MemoryGrowth,
+    /// Called with the new capacity after each chunk grown by [`SharedMemory::resize`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_chunk: Option<fn(usize)>,
+}
+
+/// Strategy used by [`SharedMemory`] to grow its buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MemoryGrowth {
+    /// Allocates the requested size at once.
+    #[default]
+    Exact,
+    /// Grows the allocation by at most the given number of bytes at a time.
+    Chunked(usize),
 }
 
 /// Empty shared memory.
@@ -37,6 +55,8 @@ pub const EMPTY_SHARED_MEMORY: SharedMemory = SharedMemory {
     last_checkpoint: 0,
     #[cfg(feature = "memory_limit")]
     memory_limit: u64::MAX,
+    growth: MemoryGrowth::Exact,
+    on_chunk: None,
 };
 
 impl fmt::Debug for SharedMemory {
@@ -48,6 +68,33 @@ impl fmt::Debug for SharedMemory {
     }
 }
 
+// The growth callback is not part of the memory state and is ignored.
+impl PartialEq for SharedMemory {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "memory_limit")]
+        if self.memory_limit != other.memory_limit {
+            return false;
+        }
+        self.buffer == other.buffer
+            && self.checkpoints == other.checkpoints
+            && self.last_checkpoint == other.last_checkpoint
+            && self.growth == other.growth
+    }
+}
+
+impl Eq for SharedMemory {}
+
+impl Hash for SharedMemory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buffer.hash(state);
+        self.checkpoints.hash(state);
+        self.last_checkpoint.hash(state);
+        #[cfg(feature = "memory_limit")]
+        self.memory_limit.hash(state);
+        self.growth.hash(state);
+    }
+}
+
 impl Default for SharedMemory {
     #[inline]
     fn default() -> Self {
@@ -80,7 +127,20 @@ impl<T: MemoryGetter> MemoryTrait for Rc<RefCell<T>> {
     }
 
     fn set(&mut self, memory_offset: usize, data: &[u8]) {
-        self.borrow_mut().memory_mut().set(memory_offset, data);
+        self.borrow_mut()
+            .memory_mut()
+            .set_unchecked(memory_offset, data);
+    }
+
+    fn copy_from_slice_checked(
+        &mut self,
+        offset: usize,
+        src: &[u8],
+        len: usize,
+    ) -> Result<(), InstructionResult> {
+        self.borrow_mut()
+            .memory_mut()
+            .copy_from_slice_checked(offset, src, len)
     }
 
     fn size(&self) -> usize {
@@ -121,6 +181,8 @@ impl SharedMemory {
             last_checkpoint: 0,
             #[cfg(feature = "memory_limit")]
             memory_limit: u64::MAX,
+            growth: MemoryGrowth::Exact,
+            on_chunk: None,
         }
     }
 
@@ -163,6 +225,16 @@ impl SharedMemory {
         }
     }
 
+    /// Removes all contexts and their memory.
+    ///
+    /// Capacity of the buffer is retained.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.checkpoints.clear();
+        self.last_checkpoint = 0;
+    }
+
     /// Returns the length of the current memory range.
     #[inline]
     pub fn len(&self) -> usize {
@@ -175,10 +247,49 @@ impl SharedMemory {
         self.len() == 0
     }
 
+    /// Sets the strategy used to grow the buffer.
+    #[inline]
+    pub fn set_growth(&mut self, growth: MemoryGrowth) {
+        self.growth = growth;
+    }
+
+    /// Sets the callback called with the new capacity after each chunk grown by
+    /// [`SharedMemory::resize`], which is used by the interpreter to expand memory.
+    ///
+    /// Only called with [`MemoryGrowth::Chunked`].
+    #[inline]
+    pub fn set_growth_callback(&mut self, on_chunk: Option<fn(usize)>) {
+        self.on_chunk = on_chunk;
+    }
+
     /// Resizes the memory in-place so that `len` is equal to `new_len`.
+    ///
+    /// Calls the [growth callback][SharedMemory::set_growth_callback] if one is set.
     #[inline]
     pub fn resize(&mut self, new_size: usize) {
-        self.buffer.resize(self.last_checkpoint + new_size, 0);
+        match self.on_chunk {
+            Some(on_chunk) => self.resize_with(new_size, on_chunk),
+            None => self.resize_with(new_size, |_| {}),
+        }
+    }
+
+    /// Resizes the memory in-place so that `len` is equal to `new_len`.
+    ///
+    /// With [`MemoryGrowth::Chunked`] the buffer allocation is grown one chunk at a time
+    /// and `on_chunk` is called with the new capacity after each chunk.
+    #[inline]
+    pub fn resize_with(&mut self, new_size: usize, mut on_chunk: impl FnMut(usize)) {
+        let new_len = self.last_checkpoint + new_size;
+        if let MemoryGrowth::Chunked(chunk) = self.growth {
+            let chunk = chunk.max(1);
+            while self.buffer.capacity() < new_len {
+                let capacity = self.buffer.capacity();
+                let target = capacity + chunk.min(new_len - capacity);
+                self.buffer.reserve_exact(target - self.buffer.len());
+                on_chunk(self.buffer.capacity());
+            }
+        }
+        self.buffer.resize(new_len, 0);
     }
 
     /// Returns a byte slice of the memory region at the given offset.
@@ -241,6 +352,19 @@ impl SharedMemory {
         self.slice_len(offset, 32).try_into().unwrap()
     }
 
+    /// Returns a 32-byte word of the memory region at the given offset.
+    ///
+    /// Bytes past the end of the memory are read as zeros, the memory is not resized.
+    #[inline]
+    pub fn get_word_padded(&self, offset: usize) -> B256 {
+        let mut word = B256::ZERO;
+        if let Some(src) = self.context_memory().get(offset..) {
+            let len = src.len().min(32);
+            word[..len].copy_from_slice(&src[..len]);
+        }
+        word
+    }
+
     /// Returns a U256 of the memory region at the given offset.
     ///
     /// # Panics
@@ -259,7 +383,7 @@ impl SharedMemory {
     #[inline]
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn set_byte(&mut self, offset: usize, byte: u8) {
-        self.set(offset, &[byte]);
+        self.set_unchecked(offset, &[byte]);
     }
 
     /// Sets the given 32-byte `value` to the memory region at the given `offset`.
@@ -270,7 +394,24 @@ impl SharedMemory {
     #[inline]
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn set_word(&mut self, offset: usize, value: &B256) {
-        self.set(offset, &value[..]);
+        self.set_unchecked(offset, &value[..]);
+    }
+
+    /// Sets the given 32-byte `value` to the memory region at the given `offset`.
+    ///
+    /// Memory is resized to the next multiple of 32 bytes if the word does not fit.
+    /// Memory expansion gas is not charged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + 32` overflows.
+    #[inline]
+    pub fn set_word_resize(&mut self, offset: usize, value: &B256) {
+        let end = offset.checked_add(32).expect("memory offset overflow");
+        if end > self.len() {
+            self.resize(num_words(end) * 32);
+        }
+        self.set_word(offset, value);
     }
 
     /// Sets the given U256 `value` to the memory region at the given `offset`.
@@ -281,7 +422,7 @@ impl SharedMemory {
     #[inline]
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn set_u256(&mut self, offset: usize, value: U256) {
-        self.set(offset, &value.to_be_bytes::<32>());
+        self.set_unchecked(offset, &value.to_be_bytes::<32>());
     }
 
     /// Set memory region at given `offset`.
@@ -291,12 +432,43 @@ impl SharedMemory {
     /// Panics on out of bounds.
     #[inline]
     #[cfg_attr(debug_assertions, track_caller)]
+    #[deprecated = "use `SharedMemory::copy_from_slice_checked` instead"]
     pub fn set(&mut self, offset: usize, value: &[u8]) {
+        self.set_unchecked(offset, value);
+    }
+
+    #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn set_unchecked(&mut self, offset: usize, value: &[u8]) {
         if !value.is_empty() {
             self.slice_mut(offset, value.len()).copy_from_slice(value);
         }
     }
 
+    /// Copies `len` bytes of `src` to memory at `offset`.
+    ///
+    /// If `src` is shorter than `len` the rest of the region is zeroed, extra `src` bytes
+    /// are ignored. Returns [`InstructionResult::OutOfOffset`] and leaves memory unchanged
+    /// if the region does not fit in the current memory. Copies of zero length always
+    /// succeed, whatever the offset.
+    #[inline]
+    pub fn copy_from_slice_checked(
+        &mut self,
+        offset: usize,
+        src: &[u8],
+        len: usize,
+    ) -> Result<(), InstructionResult> {
+        match offset.checked_add(len) {
+            _ if len == 0 => return Ok(()),
+            Some(end) if end <= self.len() => {}
+            _ => return Err(InstructionResult::OutOfOffset),
+        }
+        let copied = min(len, src.len());
+        self.set_unchecked(offset, &src[..copied]);
+        self.slice_mut(offset + copied, len - copied).fill(0);
+        Ok(())
+    }
+
     /// Set memory from data. Our memory offset+len is expected to be correct but we
     /// are doing bound checks on data/data_offeset/len and zeroing parts that is not copied.
     ///
@@ -364,6 +536,7 @@ pub const fn num_words(len: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_num_words() {
@@ -378,6 +551,45 @@ mod tests {
         assert_eq!(num_words(usize::MAX), usize::MAX / 32);
     }
 
+    #[test]
+    fn word_padded_resize() {
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        assert_eq!(shared_memory.get_word_padded(0), B256::ZERO);
+
+        let word = B256::repeat_byte(0xAB);
+        shared_memory.set_word_resize(1, &word);
+        assert_eq!(shared_memory.len(), 64);
+        assert_eq!(shared_memory.get_word_padded(1), word);
+        assert_eq!(shared_memory.get_word(1), word);
+
+        // Partially past the end, zero padded.
+        shared_memory.set_word_resize(32, &word);
+        assert_eq!(shared_memory.len(), 64);
+        let mut expected = B256::ZERO;
+        expected[..16].fill(0xAB);
+        assert_eq!(shared_memory.get_word_padded(48), expected);
+        assert_eq!(shared_memory.get_word_padded(usize::MAX), B256::ZERO);
+        assert_eq!(shared_memory.len(), 64);
+    }
+
+    #[test]
+    fn clear() {
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(32);
+        shared_memory.new_context();
+        shared_memory.resize(64);
+        let capacity = shared_memory.buffer.capacity();
+
+        shared_memory.clear();
+        assert!(shared_memory.is_empty());
+        assert!(shared_memory.buffer.is_empty());
+        assert!(shared_memory.checkpoints.is_empty());
+        assert_eq!(shared_memory.last_checkpoint, 0);
+        assert_eq!(shared_memory.buffer.capacity(), capacity);
+    }
+
     #[test]
     fn new_free_context() {
         let mut shared_memory = SharedMemory::new();
@@ -450,4 +662,72 @@ mod tests {
         assert_eq!(shared_memory.len(), 64);
         assert_eq!(shared_memory.buffer.get(0..64), Some(&[0_u8; 64] as &[u8]));
     }
+
+    #[test]
+    fn resize_chunked() {
+        let mut shared_memory = SharedMemory::with_capacity(0);
+        shared_memory.set_growth(MemoryGrowth::Chunked(64 * 1024));
+        shared_memory.new_context();
+
+        let mut chunks = 0;
+        shared_memory.resize_with(1024 * 1024, |_| chunks += 1);
+        assert_eq!(chunks, 16);
+        assert_eq!(shared_memory.len(), 1024 * 1024);
+
+        // Memory expansion during execution calls the growth callback.
+        static CHUNKS: AtomicUsize = AtomicUsize::new(0);
+        let mut memory = Rc::new(RefCell::new(SharedMemory::with_capacity(0)));
+        memory
+            .borrow_mut()
+            .set_growth(MemoryGrowth::Chunked(64 * 1024));
+        memory.borrow_mut().set_growth_callback(Some(|_| {
+            CHUNKS.fetch_add(1, Ordering::Relaxed);
+        }));
+        memory.borrow_mut().new_context();
+        assert!(MemoryTrait::resize(&mut memory, 1024 * 1024));
+        assert_eq!(CHUNKS.load(Ordering::Relaxed), 16);
+
+        // Exact growth allocates at once without chunks.
+        let mut shared_memory = SharedMemory::with_capacity(0);
+        shared_memory.new_context();
+        let mut chunks = 0;
+        shared_memory.resize_with(1024 * 1024, |_| chunks += 1);
+        assert_eq!(chunks, 0);
+        assert_eq!(shared_memory.len(), 1024 * 1024);
+    }
+
+    #[test]
+    fn copy_from_slice_checked() {
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(64);
+        shared_memory.slice_mut(0, 64).fill(0xff);
+
+        // Source shorter than `len` is padded with zeros.
+        assert_eq!(shared_memory.copy_from_slice_checked(2, &[1, 2], 4), Ok(()));
+        assert_eq!(
+            shared_memory.slice_len(0, 7),
+            &[0xff, 0xff, 1, 2, 0, 0, 0xff]
+        );
+        // Extra source bytes are ignored.
+        assert_eq!(
+            shared_memory.copy_from_slice_checked(62, &[3, 4, 5], 2),
+            Ok(())
+        );
+        assert_eq!(shared_memory.slice_len(62, 2), &[3, 4]);
+
+        assert_eq!(
+            shared_memory.copy_from_slice_checked(63, &[6, 7], 2),
+            Err(InstructionResult::OutOfOffset)
+        );
+        assert_eq!(
+            shared_memory.copy_from_slice_checked(usize::MAX, &[], 1),
+            Err(InstructionResult::OutOfOffset)
+        );
+        assert_eq!(
+            shared_memory.copy_from_slice_checked(usize::MAX, &[], 0),
+            Ok(())
+        );
+        assert_eq!(shared_memory.get_byte(63), 4);
+    }
 }
@@ -9,11 +9,16 @@ use super::StackTrait;
 pub const STACK_LIMIT: usize = 1024;
 
 /// EVM stack with [STACK_LIMIT] capacity of words.
+///
+/// Use [`Stack::with_capacity`] for a different initial allocation or limit.
 #[derive(Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stack {
     /// The underlying data of the stack.
     data: Vec<U256>,
+    /// The maximum length the stack has reached.
+    max_len: usize,
+    /// The maximum number of words the stack can hold.
+    limit: usize,
 }
 
 impl fmt::Display for Stack {
@@ -38,11 +43,11 @@ impl Default for Stack {
 
 impl Clone for Stack {
     fn clone(&self) -> Self {
-        // Use `Self::new()` to ensure the cloned Stack maintains the STACK_LIMIT capacity,
-        // and then copy the data. This preserves the invariant that Stack always has
-        // STACK_LIMIT capacity, which is crucial for the safety and correctness of other methods.
-        let mut new_stack = Self::new();
+        // Use `Self::with_capacity` to ensure the cloned Stack maintains the capacity and
+        // limit of the original, and then copy the data.
+        let mut new_stack = Self::with_capacity(self.data.capacity(), self.limit);
         new_stack.data.extend_from_slice(&self.data);
+        new_stack.max_len = self.max_len;
         new_stack
     }
 }
@@ -87,12 +92,31 @@ impl Stack {
     /// Instantiate a new stack with the [default stack limit][STACK_LIMIT].
     #[inline]
     pub fn new() -> Self {
+        Self::with_capacity(STACK_LIMIT, STACK_LIMIT)
+    }
+
+    /// Instantiate a new stack that holds at most `max_depth` words and allocates `capacity`
+    /// words.
+    ///
+    /// `capacity` is raised to `max_depth` if it is smaller, so that pushing within the limit
+    /// never reallocates. A limit other than [STACK_LIMIT] does not conform to the EVM
+    /// specification.
+    #[inline]
+    pub fn with_capacity(capacity: usize, max_depth: usize) -> Self {
         Self {
-            // SAFETY: Expansion functions assume that capacity is `STACK_LIMIT`.
-            data: Vec::with_capacity(STACK_LIMIT),
+            // SAFETY: Expansion functions assume that capacity is at least `limit`.
+            data: Vec::with_capacity(capacity.max(max_depth)),
+            max_len: 0,
+            limit: max_depth,
         }
     }
 
+    /// Returns the maximum number of words the stack can hold.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
     /// Returns the length of the stack in words.
     #[inline]
     pub fn len(&self) -> usize {
@@ -105,6 +129,24 @@ impl Stack {
         self.data.is_empty()
     }
 
+    /// Returns the maximum length in words the stack has reached.
+    ///
+    /// Only pushes done through the [`Stack`] API are tracked, writes to
+    /// [`data_mut`][Stack::data_mut] are not.
+    #[inline]
+    pub fn max_depth(&self) -> usize {
+        self.max_len
+    }
+
+    /// Removes all values from the stack and resets the [maximum depth][Stack::max_depth].
+    ///
+    /// Capacity of the stack is retained.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.max_len = 0;
+    }
+
     /// Returns a reference to the underlying data buffer.
     #[inline]
     pub fn data(&self) -> &Vec<U256> {
@@ -194,11 +236,12 @@ impl Stack {
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn push(&mut self, value: U256) -> bool {
         // Allows the compiler to optimize out the `Vec::push` capacity check.
-        assume!(self.data.capacity() == STACK_LIMIT);
-        if self.data.len() == STACK_LIMIT {
+        assume!(self.data.capacity() >= self.limit);
+        if self.data.len() >= self.limit {
             return false;
         }
         self.data.push(value);
+        self.max_len = self.max_len.max(self.data.len());
         true
     }
 
@@ -214,6 +257,30 @@ impl Stack {
         }
     }
 
+    /// Returns a reference to the value at given index for the stack, where the top of
+    /// the stack is at index `0`. If the index is too large,
+    /// `StackError::Underflow` is returned.
+    #[inline]
+    pub fn peek_n(&self, no_from_top: usize) -> Result<&U256, InstructionResult> {
+        if self.data.len() > no_from_top {
+            Ok(&self.data[self.data.len() - no_from_top - 1])
+        } else {
+            Err(InstructionResult::StackUnderflow)
+        }
+    }
+
+    /// Returns the top `n` values of the stack in stack order, the top of the stack is the
+    /// last element. If `n` is larger than the stack length, `StackError::Underflow` is
+    /// returned.
+    #[inline]
+    pub fn peek_slice(&self, n: usize) -> Result<&[U256], InstructionResult> {
+        if self.data.len() >= n {
+            Ok(&self.data[self.data.len() - n..])
+        } else {
+            Err(InstructionResult::StackUnderflow)
+        }
+    }
+
     /// Duplicates the `N`th value from the top of the stack.
     ///
     /// # Panics
@@ -225,7 +292,7 @@ impl Stack {
     pub fn dup(&mut self, n: usize) -> bool {
         assume!(n > 0, "attempted to dup 0");
         let len = self.data.len();
-        if len < n || len + 1 > STACK_LIMIT {
+        if len < n || len + 1 > self.limit {
             false
         } else {
             // SAFETY: Check for out of bounds is done above and it makes this safe to do.
@@ -234,6 +301,7 @@ impl Stack {
                 ptr::copy_nonoverlapping(ptr.sub(n), ptr, 1);
                 self.data.set_len(len + 1);
             }
+            self.max_len = self.max_len.max(len + 1);
             true
         }
     }
@@ -285,11 +353,12 @@ impl Stack {
             return Ok(());
         }
 
-        let n_words = (slice.len() + 31) / 32;
+        let n_words = slice.len().div_ceil(32);
         let new_len = self.data.len() + n_words;
-        if new_len > STACK_LIMIT {
+        if new_len > self.limit {
             return Err(InstructionResult::StackOverflow);
         }
+        self.max_len = self.max_len.max(new_len);
 
         // SAFETY: Length checked above.
         unsafe {
@@ -330,7 +399,7 @@ impl Stack {
                 i += 1;
             }
 
-            debug_assert_eq!((i + 3) / 4, n_words, "wrote too much");
+            debug_assert_eq!(i.div_ceil(4), n_words, "wrote too much");
 
             // Zero out upper bytes of last word
             let m = i % 4; // 32 / 8
@@ -357,22 +426,57 @@ impl Stack {
     }
 }
 
+/// Serialized form of [`Stack`], shared by the `Serialize` and `Deserialize` impls.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StackSerde<D> {
+    data: D,
+    #[serde(default = "stack_limit")]
+    limit: usize,
+}
+
+#[cfg(feature = "serde")]
+const fn stack_limit() -> usize {
+    STACK_LIMIT
+}
+
+/// Serialized as the stack values and the limit, in the same format
+/// [`Deserialize`][serde::Deserialize] expects.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Stack {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        StackSerde {
+            data: &self.data,
+            limit: self.limit,
+        }
+        .serialize(serializer)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for Stack {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let mut data = Vec::<U256>::deserialize(deserializer)?;
-        if data.len() > STACK_LIMIT {
+        let StackSerde::<Vec<U256>> { mut data, limit } = StackSerde::deserialize(deserializer)?;
+        if data.len() > limit {
             return Err(serde::de::Error::custom(std::format!(
                 "stack size exceeds limit: {} > {}",
                 data.len(),
-                STACK_LIMIT
+                limit
             )));
         }
-        data.reserve(STACK_LIMIT - data.len());
-        Ok(Self { data })
+        data.reserve_exact(limit - data.len());
+        let max_len = data.len();
+        Ok(Self {
+            data,
+            max_len,
+            limit,
+        })
     }
 }
 
@@ -391,6 +495,60 @@ mod tests {
         f(&mut stack);
     }
 
+    #[test]
+    fn with_capacity() {
+        let mut stack = Stack::with_capacity(2, 4);
+        assert_eq!(stack.limit(), 4);
+        assert!(stack.data.capacity() >= 4);
+        for i in 0..3 {
+            assert!(stack.push(U256::from(i)));
+        }
+        assert!(stack.dup(1));
+        assert!(!stack.push(U256::ZERO));
+        assert!(!stack.dup(1));
+        assert_eq!(stack.len(), 4);
+
+        stack.clear();
+        assert_eq!(stack.limit(), 4);
+        assert_eq!(
+            stack.push_slice(&[0xFF; 5 * 32]),
+            Err(InstructionResult::StackOverflow)
+        );
+        assert_eq!(stack.push_slice(&[0xFF; 4 * 32]), Ok(()));
+        assert_eq!(stack.data, [U256::MAX; 4]);
+
+        let cloned = stack.clone();
+        assert_eq!(cloned.limit(), 4);
+        assert_eq!(cloned, stack);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&stack).unwrap();
+            let deserialized: Stack = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.limit(), 4);
+            assert_eq!(deserialized, stack);
+            assert!(serde_json::from_str::<Stack>(r#"{"data":["0x1","0x2"],"limit":1}"#).is_err());
+        }
+    }
+
+    #[test]
+    fn peek_n_and_slice() {
+        run(|stack| {
+            for i in 1..=3 {
+                assert!(stack.push(U256::from(i)));
+            }
+            assert_eq!(stack.peek_n(0), Ok(&U256::from(3)));
+            assert_eq!(stack.peek_n(2), Ok(&U256::from(1)));
+            assert_eq!(stack.peek_n(3), Err(InstructionResult::StackUnderflow));
+
+            assert_eq!(stack.peek_slice(0), Ok(&[][..]));
+            assert_eq!(stack.peek_slice(2), Ok(&[U256::from(2), U256::from(3)][..]));
+            assert_eq!(stack.peek_slice(3), Ok(&stack.data[..]));
+            assert_eq!(stack.peek_slice(4), Err(InstructionResult::StackUnderflow));
+            assert_eq!(stack.len(), 3);
+        });
+    }
+
     #[test]
     fn push_slices() {
         // No-op
@@ -1,5 +1,5 @@
 use crate::{Gas, InstructionResult, InterpreterResult};
-use core::ops::Range;
+use core::{cmp::min, ops::Range};
 use primitives::Bytes;
 
 /// Represents the outcome of a call operation in a virtual machine.
@@ -88,4 +88,51 @@ impl CallOutcome {
     pub fn memory_length(&self) -> usize {
         self.memory_offset.len()
     }
+
+    /// Returns the part of the output that is copied to the memory range.
+    ///
+    /// The output is truncated to the length of the memory range. If the range is empty or
+    /// malformed (`end <= start`), empty [`Bytes`] are returned.
+    ///
+    /// # Returns
+    ///
+    /// The output data that fits into the memory range as [`Bytes`].
+    pub fn memory_output(&self) -> Bytes {
+        if self.memory_offset.end <= self.memory_offset.start {
+            return Bytes::new();
+        }
+        let len = min(self.memory_length(), self.result.output.len());
+        self.result.output.slice(..len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::bytes;
+
+    fn outcome(memory_offset: Range<usize>) -> CallOutcome {
+        CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Return, bytes!("0102030405"), Gas::new(0)),
+            memory_offset,
+        )
+    }
+
+    #[test]
+    fn memory_output_truncated_to_range() {
+        assert_eq!(outcome(10..13).memory_output(), bytes!("010203"));
+        assert_eq!(outcome(10..20).memory_output(), bytes!("0102030405"));
+        assert_eq!(
+            outcome(usize::MAX..usize::MAX).memory_output(),
+            Bytes::new()
+        );
+    }
+
+    #[test]
+    fn memory_output_inverted_range() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let outcome = outcome(20..10);
+        assert_eq!(outcome.memory_length(), 0);
+        assert_eq!(outcome.memory_output(), Bytes::new());
+    }
 }
@@ -28,3 +28,42 @@ impl CreateInputs {
         }
     }
 }
+
+/// Returns the address of a contract deployed by `CREATE2` from `sender`.
+///
+/// Address is the last 20 bytes of
+/// `keccak256(0xff ++ sender ++ salt ++ keccak256(init_code))` as defined in
+/// [EIP-1014](https://eips.ethereum.org/EIPS/eip-1014).
+#[inline]
+pub fn predict_create2(sender: Address, salt: U256, init_code: &[u8]) -> Address {
+    sender.create2_from_code(salt.to_be_bytes(), init_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, hex};
+
+    #[test]
+    fn predict_create2_eip1014() {
+        // Example 0.
+        assert_eq!(
+            predict_create2(Address::ZERO, U256::ZERO, &[0x00]),
+            address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+        );
+        // Example 5.
+        assert_eq!(
+            predict_create2(
+                address!("00000000000000000000000000000000deadbeef"),
+                U256::from(0xcafebabe_u64),
+                &hex!("deadbeef")
+            ),
+            address!("60f3f640a8508fC6a86d45DF051962668E1e8AC7")
+        );
+        // Example 6, empty init code.
+        assert_eq!(
+            predict_create2(Address::ZERO, U256::ZERO, &[]),
+            address!("E33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0")
+        );
+    }
+}
@@ -50,6 +50,26 @@ pub trait MemoryTrait {
     fn set_data(&mut self, memory_offset: usize, data_offset: usize, len: usize, data: &[u8]);
     fn set(&mut self, memory_offset: usize, data: &[u8]);
 
+    /// Copies `len` bytes of `src` to memory at `offset`, zeroing the part not covered by
+    /// `src`.
+    ///
+    /// Returns [`InstructionResult::OutOfOffset`] if the region is out of scope of
+    /// allocated memory. Copies of zero length always succeed.
+    fn copy_from_slice_checked(
+        &mut self,
+        offset: usize,
+        src: &[u8],
+        len: usize,
+    ) -> Result<(), InstructionResult> {
+        match offset.checked_add(len) {
+            _ if len == 0 => return Ok(()),
+            Some(end) if end <= self.size() => {}
+            _ => return Err(InstructionResult::OutOfOffset),
+        }
+        self.set_data(offset, 0, len, src);
+        Ok(())
+    }
+
     fn size(&self) -> usize;
     fn copy(&mut self, destination: usize, source: usize, len: usize);
 
@@ -66,6 +86,13 @@ pub trait MemoryTrait {
         self.slice(offset..offset + len)
     }
 
+    /// Returns 32-byte word at the given offset.
+    ///
+    /// Uses [`slice_len`][MemoryTrait::slice_len] internally.
+    fn get_word(&self, offset: usize) -> B256 {
+        B256::from_slice(&self.slice_len(offset, 32))
+    }
+
     /// Resizes memory to new size
     ///
     /// # Note
@@ -27,18 +27,21 @@ pub mod table;
 
 // Reexport primary types.
 pub use context_interface::{
-    host::{DummyHost, Host, SStoreResult, SelfDestructResult, StateLoad},
+    host::{
+        CallGasBreakdown, DummyHost, EmptyHost, Host, SStoreResult, SelfDestructResult, StateLoad,
+    },
     CreateScheme,
 };
-pub use gas::Gas;
+pub use gas::{Gas, GasCheckpoint, GasReport, RemainingGas};
 pub use instruction_result::*;
 pub use interpreter::{
-    num_words, InputsImpl, Interpreter, InterpreterResult, MemoryGetter, SharedMemory, Stack,
-    EMPTY_SHARED_MEMORY, STACK_LIMIT,
+    execute_init_code, is_gas_sensitive, minimum_gas_limit, num_words, InputsImpl, Interpreter,
+    InterpreterBuildError, InterpreterBuilder, InterpreterConfig, InterpreterResult, MemoryGetter,
+    MemoryGrowth, RunOutcome, SharedMemory, Stack, EMPTY_SHARED_MEMORY, STACK_LIMIT,
 };
 pub use interpreter_action::{
-    CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, EOFCreateInputs,
-    EOFCreateKind, FrameInput, InterpreterAction,
+    predict_create2, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
+    EOFCreateInputs, EOFCreateKind, FrameInput, InterpreterAction,
 };
 pub use interpreter_types::InterpreterTypes;
 pub use specification::constants::{MAX_CODE_SIZE, MAX_INITCODE_SIZE};
@@ -35,7 +35,7 @@ const INPUT_LENGTH: usize = 384;
 /// See also: <https://eips.ethereum.org/EIPS/eip-2537#abi-for-pairing>
 pub(super) fn pairing(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let input_len = input.len();
-    if input_len == 0 || input_len % INPUT_LENGTH != 0 {
+    if input_len == 0 || !input_len.is_multiple_of(INPUT_LENGTH) {
         return Err(PrecompileError::Other(format!(
             "Pairing input length should be multiple of {INPUT_LENGTH}, was {input_len}"
         ))
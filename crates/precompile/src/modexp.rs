@@ -150,7 +150,7 @@ pub fn berlin_gas_calc(
     fn calculate_multiplication_complexity(base_length: u64, mod_length: u64) -> U256 {
         let max_length = max(base_length, mod_length);
         let mut words = max_length / 8;
-        if max_length % 8 > 0 {
+        if !max_length.is_multiple_of(8) {
             words += 1;
         }
         let words = U256::from(words);
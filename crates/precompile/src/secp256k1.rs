@@ -2,13 +2,45 @@ use crate::{
     utilities::right_pad, PrecompileError, PrecompileOutput, PrecompileResult,
     PrecompileWithAddress,
 };
-use primitives::{alloy_primitives::B512, Bytes, B256};
+use primitives::{alloy_primitives::B512, uint, Address, Bytes, B256, U256};
 
 pub const ECRECOVER: PrecompileWithAddress =
     PrecompileWithAddress(crate::u64_to_address(1), ec_recover_run);
 
 pub use self::secp256k1::ecrecover;
 
+/// Order of the secp256k1 curve.
+const SECP256K1N: U256 =
+    uint!(0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141_U256);
+
+/// Half of the order of the secp256k1 curve, the upper bound for `s` since EIP-2.
+const SECP256K1N_HALF: U256 =
+    uint!(0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256);
+
+/// Recovers the signer address of `hash` from the signature `(v, r, s)`.
+///
+/// `v` can be either the recovery id (`0` or `1`) or the legacy `27`/`28` value.
+///
+/// Returns `None` if `v` is invalid, `r` or `s` are out of range (`s` must be in the lower
+/// half of the curve order as per EIP-2), or the public key can't be recovered.
+pub fn recover_signer(hash: B256, v: u8, r: U256, s: U256) -> Option<Address> {
+    let recid = match v {
+        0 | 1 => v,
+        27 | 28 => v - 27,
+        _ => return None,
+    };
+    if r.is_zero() || r >= SECP256K1N || s.is_zero() || s > SECP256K1N_HALF {
+        return None;
+    }
+
+    let mut sig = B512::ZERO;
+    sig[..32].copy_from_slice(&r.to_be_bytes::<32>());
+    sig[32..].copy_from_slice(&s.to_be_bytes::<32>());
+
+    let hash = ecrecover(&sig, recid, &hash).ok()?;
+    Some(Address::from_word(hash))
+}
+
 #[cfg(not(feature = "secp256k1"))]
 #[allow(clippy::module_inception)]
 mod secp256k1 {
@@ -89,3 +121,62 @@ pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
         .unwrap_or_default();
     Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+    use primitives::{address, keccak256};
+
+    /// Signs `hash` with the private key `1`, returning `(v, r, s)`.
+    fn sign(hash: B256) -> (u8, U256, U256) {
+        let key = SigningKey::from_slice(&B256::with_last_byte(1)[..]).unwrap();
+        let (sig, recid) = key.sign_prehash_recoverable(&hash[..]).unwrap();
+        let r = U256::from_be_slice(&sig.r().to_bytes());
+        let s = U256::from_be_slice(&sig.s().to_bytes());
+        (recid.to_byte(), r, s)
+    }
+
+    #[test]
+    fn recover_signer_known_key() {
+        // Address of the private key `1`.
+        let signer = address!("7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+
+        let hash = keccak256("hello");
+        let (v, r, s) = sign(hash);
+        assert_eq!(recover_signer(hash, v, r, s), Some(signer));
+        assert_eq!(recover_signer(hash, v + 27, r, s), Some(signer));
+
+        // Matches the precompile output.
+        let mut input = [0u8; 128];
+        input[..32].copy_from_slice(hash.as_slice());
+        input[63] = v + 27;
+        input[64..96].copy_from_slice(&r.to_be_bytes::<32>());
+        input[96..].copy_from_slice(&s.to_be_bytes::<32>());
+        let out = ec_recover_run(&Bytes::copy_from_slice(&input), 3_000).unwrap();
+        assert_eq!(
+            out.bytes[..],
+            B256::left_padding_from(signer.as_slice())[..]
+        );
+    }
+
+    #[test]
+    fn recover_signer_invalid() {
+        let hash = keccak256("hello");
+        let (v, r, s) = sign(hash);
+
+        // High `s` is rejected as per EIP-2.
+        assert_eq!(recover_signer(hash, v, r, SECP256K1N - s), None);
+        assert_eq!(
+            recover_signer(hash, v, r, SECP256K1N_HALF + U256::from(1)),
+            None
+        );
+        // Out of range or zero `r` and `s`.
+        assert_eq!(recover_signer(hash, v, SECP256K1N, s), None);
+        assert_eq!(recover_signer(hash, v, U256::ZERO, s), None);
+        assert_eq!(recover_signer(hash, v, r, U256::ZERO), None);
+        // Invalid `v`.
+        assert_eq!(recover_signer(hash, 2, r, s), None);
+        assert_eq!(recover_signer(hash, 29, r, s), None);
+    }
+}
@@ -4,7 +4,7 @@ use context_interface::{
     block::BlockSetter,
     journaled_state::Journal,
     result::{
-        EVMError, ExecutionResult, HaltReasonTrait, InvalidHeader, InvalidTransaction,
+        EVMError, ExecutionResult, HaltReason, HaltReasonTrait, InvalidHeader, InvalidTransaction,
         ResultAndState,
     },
     transaction::TransactionSetter,
@@ -19,7 +19,7 @@ use handler_interface::{
 };
 use interpreter::Host;
 use precompile::PrecompileErrors;
-use primitives::Log;
+use primitives::{Address, Bytes, Log, TxKind};
 use state::EvmState;
 use std::vec::Vec;
 
@@ -145,6 +145,33 @@ pub type EthContext<DB, BLOCK = BlockEnv, TX = TxEnv, CFG = CfgEnv, JOURNAL = Jo
 /// Mainnet EVM type.
 pub type MainEvm<DB, BLOCK, TX, CFG> = Evm<Error<DB>, EthContext<DB, BLOCK, TX, CFG>>;
 
+impl<DB: Database> MainEvm<DB, BlockEnv, TxEnv, CfgEnv> {
+    /// Simulates a call to `target` without committing state changes to the database.
+    ///
+    /// The transaction destination, input and gas limit are overridden, other fields are
+    /// taken from the current transaction, which is restored afterwards. As for any
+    /// transaction, `gas_limit` includes the intrinsic gas (21000 for a call without input).
+    ///
+    /// Returned [`ExecutionResult`] contains the output and gas used, while all state
+    /// changes of the call are discarded.
+    pub fn simulate_call(
+        &mut self,
+        target: Address,
+        input: Bytes,
+        gas_limit: u64,
+    ) -> Result<ExecutionResult<HaltReason>, Error<DB>> {
+        let tx = self.context.tx.clone();
+        self.context.modify_tx(|tx| {
+            tx.transact_to = TxKind::Call(target);
+            tx.data = input;
+            tx.gas_limit = gas_limit;
+        });
+        let result = self.transact().map(|output| output.result);
+        self.context.tx = tx;
+        result
+    }
+}
+
 impl<ERROR, CTX, VAL, PREEXEC, EXEC, POSTEXEC>
     Evm<ERROR, CTX, EthHandler<CTX, ERROR, VAL, PREEXEC, EXEC, POSTEXEC>>
 where
@@ -452,3 +479,366 @@ mod tests {
 }
 
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{
+            CALL, DELEGATECALL, JUMPDEST, JUMPI, MSTORE, POP, PUSH1, PUSH2, RETURN, REVERT, SLOAD,
+            SSTORE, STATICCALL, STOP, TLOAD, TSTORE,
+        },
+        Bytecode,
+    };
+    use context_interface::result::EVMError;
+    use core::{convert::Infallible, marker::PhantomData, num::NonZeroU64};
+    use database::InMemoryDB;
+    use handler::EthPrecompileProvider;
+    use handler_interface::PrecompileProvider;
+    use interpreter::gas::{
+        memory_gas, memory_gas_with_quad_coeff_div, CALLVALUE, CALL_STIPEND,
+        COLD_ACCOUNT_ACCESS_COST, COLD_SLOAD_COST, MEMORY_QUAD_COEFF_DIV, NEWACCOUNT, SSTORE_SET,
+        VERYLOW,
+    };
+    use interpreter::{
+        interpreter::EthInterpreter, is_gas_sensitive, minimum_gas_limit,
+        table::make_instruction_table, Host, InputsImpl, InstructionResult,
+    };
+    use precompile::{PrecompileOutput, PrecompileResult, PrecompileWithAddress, Precompiles};
+    use primitives::{address, Address, U256};
+    use specification::hardfork::SpecId;
+    use state::AccountInfo;
+
+    /// Account the test code is deployed at.
+    const TARGET: Address = address!("0000000000000000000000000000000000000100");
+
+    /// Account the test code calls into.
+    const CHILD: Address = address!("0000000000000000000000000000000000000200");
+
+    /// EVM over a database with legacy code deployed at each address.
+    fn evm_with_code(
+        accounts: &[(Address, &[u8])],
+    ) -> MainEvm<InMemoryDB, BlockEnv, TxEnv, CfgEnv> {
+        let mut db = InMemoryDB::default();
+        for (address, code) in accounts {
+            db.insert_account_info(
+                *address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.to_vec().into())),
+            );
+        }
+        MainEvm::new(Context::builder().with_db(db), EthHandler::default())
+    }
+
+    /// Deploys legacy `code` at [TARGET] and simulates a 100_000 gas call to it.
+    fn deploy_and_call(code: &[u8]) -> ExecutionResult<HaltReason> {
+        evm_with_code(&[(TARGET, code)])
+            .simulate_call(TARGET, Bytes::new(), 100_000)
+            .unwrap()
+    }
+
+    #[test]
+    fn simulate_call_discards_state() {
+        let mut evm = evm_with_code(&[(TARGET, &[PUSH1, 0x01, PUSH1, 0x00, SSTORE])]);
+        let tx = evm.context.tx.clone();
+        let result = evm.simulate_call(TARGET, Bytes::new(), 100_000).unwrap();
+        assert!(result.is_success());
+        // Intrinsic gas, two pushes and a cold `SSTORE` setting a zero slot.
+        assert_eq!(
+            result.gas_used(),
+            21_000 + 2 * VERYLOW + COLD_SLOAD_COST + SSTORE_SET
+        );
+
+        // Transaction is restored and no state is left in the journal.
+        assert_eq!(evm.context.tx, tx);
+        assert!(evm.context.journaled_state.state.is_empty());
+
+        // Slot is still unset, so simulating again costs the same.
+        let result = evm.simulate_call(TARGET, Bytes::new(), 100_000).unwrap();
+        assert_eq!(
+            result.gas_used(),
+            21_000 + 2 * VERYLOW + COLD_SLOAD_COST + SSTORE_SET
+        );
+    }
+
+    #[test]
+    fn is_gas_sensitive_reverts_state() {
+        let mut context = Context::builder().with_db(InMemoryDB::default());
+        context.journaled_state.load_account(TARGET).unwrap();
+        let table = make_instruction_table::<EthInterpreter, _>();
+        let inputs = InputsImpl {
+            target_address: TARGET,
+            ..Default::default()
+        };
+        // Returns slot zero and sets it to one.
+        let code = Bytecode::new_raw(Bytes::from([
+            PUSH1, 0x00, SLOAD, PUSH1, 0x00, MSTORE, PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x20,
+            PUSH1, 0x00, RETURN,
+        ]));
+
+        assert!(!is_gas_sensitive(
+            code,
+            inputs,
+            100_000,
+            1_000_000,
+            &table,
+            &mut context
+        ));
+        assert_eq!(context.sload(TARGET, U256::ZERO).unwrap().data, U256::ZERO);
+    }
+
+    #[test]
+    fn minimum_gas_limit_reverts_state() {
+        let mut context = Context::builder().with_db(InMemoryDB::default());
+        context.journaled_state.load_account(TARGET).unwrap();
+        let table = make_instruction_table::<EthInterpreter, _>();
+        let inputs = InputsImpl {
+            target_address: TARGET,
+            ..Default::default()
+        };
+        // Reverts if slot zero is set, otherwise sets it.
+        let code = Bytecode::new_raw(Bytes::from([
+            PUSH1, 0x00, SLOAD, PUSH1, 0x0B, JUMPI, PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP,
+            JUMPDEST, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+        ]));
+
+        assert_eq!(
+            minimum_gas_limit(code, inputs, 1_000_000, &table, &mut context),
+            Some(4 * VERYLOW + 10 + COLD_SLOAD_COST + SSTORE_SET)
+        );
+        assert_eq!(context.sload(TARGET, U256::ZERO).unwrap().data, U256::ZERO);
+    }
+
+    #[test]
+    fn memory_quad_coeff_div_from_cfg() {
+        // Expands memory to 1024 words.
+        let code = [PUSH1, 0x00, PUSH2, 0x7F, 0xE0, MSTORE];
+        let gas_used = |quad_coeff_div| {
+            let mut evm = evm_with_code(&[(TARGET, &code)]);
+            evm.context.modify_cfg(|cfg| {
+                cfg.memory_quad_coeff_div = NonZeroU64::new(quad_coeff_div).unwrap()
+            });
+            let result = evm.simulate_call(TARGET, Bytes::new(), 100_000).unwrap();
+            assert!(result.is_success());
+            result.gas_used()
+        };
+
+        assert_eq!(
+            gas_used(MEMORY_QUAD_COEFF_DIV / 2) - gas_used(MEMORY_QUAD_COEFF_DIV),
+            memory_gas_with_quad_coeff_div(1024, MEMORY_QUAD_COEFF_DIV / 2) - memory_gas(1024)
+        );
+    }
+
+    /// Calls sha256 precompile with 32 bytes of input (72 gas) forwarding `gas` and returns
+    /// the `CALL` success flag.
+    fn call_sha256(gas: u8) -> U256 {
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x20, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x02, PUSH1,
+            gas, CALL, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let result = deploy_and_call(&code);
+        assert!(result.is_success());
+        U256::from_be_slice(result.output().unwrap())
+    }
+
+    #[test]
+    fn precompile_overspending_gas_fails() {
+        fn overspending(_: &Bytes, gas_limit: u64) -> PrecompileResult {
+            Ok(PrecompileOutput::new(
+                gas_limit + 1,
+                Bytes::from_static(b"out"),
+            ))
+        }
+
+        let address = address!("0000000000000000000000000000000000000100");
+        let mut precompiles = Precompiles::default();
+        precompiles.extend([PrecompileWithAddress(address, overspending)]);
+        let mut context = Context::builder();
+        let mut provider = EthPrecompileProvider::<_, EVMError<Infallible, Infallible>> {
+            precompiles: Box::leak(Box::new(precompiles)),
+            _phantom: PhantomData,
+        };
+
+        let result = provider
+            .run(&mut context, &address, &Bytes::new(), 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileOOG);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn precompile_call_out_of_gas() {
+        assert_eq!(call_sha256(72), U256::from(1));
+        // Insufficient gas fails the call cleanly instead of reverting the caller.
+        assert_eq!(call_sha256(71), U256::ZERO);
+    }
+
+    /// Calls identity precompile with 32 bytes of input (18 gas) forwarding `gas` and returns
+    /// the gas used by the transaction.
+    fn call_identity(gas: u16) -> u64 {
+        let [gas_hi, gas_lo] = gas.to_be_bytes();
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x20, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x04, PUSH2,
+            gas_hi, gas_lo, CALL, STOP,
+        ];
+        let result = deploy_and_call(&code);
+        assert!(result.is_success());
+        result.gas_used()
+    }
+
+    #[test]
+    fn precompile_call_returns_unused_gas() {
+        // Intrinsic gas, seven pushes, memory expansion, warm `CALL` and the precompile cost.
+        let expected = 21_000 + 7 * 3 + 3 + 100 + 18;
+        assert_eq!(call_identity(18), expected);
+        // Gas forwarded above the precompile cost is returned to the caller.
+        assert_eq!(call_identity(0x1000), expected);
+    }
+
+    /// Sends 1 wei to the empty account `callee` and returns the gas used and the
+    /// callee balance.
+    fn call_empty_account_with_value(callee: Address) -> (u64, U256) {
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01, PUSH1, callee[19],
+            PUSH2, 0x27, 0x10, CALL, STOP,
+        ];
+        let mut evm = evm_with_code(&[(TARGET, &code)]);
+        let db = &mut evm.context.journaled_state.database;
+        db.accounts.get_mut(&TARGET).unwrap().info.balance = U256::from(100);
+        // Default beneficiary is address zero, which would make it warm.
+        evm.context.modify_block(|block| {
+            block.beneficiary = address!("0000000000000000000000000000000000002000")
+        });
+        evm.context.modify_tx(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000001000");
+            tx.transact_to = TxKind::Call(TARGET);
+            tx.gas_limit = 100_000;
+        });
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(state[&TARGET].info.balance, U256::from(99));
+        (result.gas_used(), state[&callee].info.balance)
+    }
+
+    #[test]
+    fn call_address_zero_with_value() {
+        let (gas_used, balance) = call_empty_account_with_value(Address::ZERO);
+        assert_eq!(balance, U256::from(1));
+        // Intrinsic gas, seven pushes and a cold `CALL` with value to a new account. The
+        // stipend is not spent by the empty callee and is returned to the caller.
+        assert_eq!(
+            gas_used,
+            21_000 + 7 * VERYLOW + COLD_ACCOUNT_ACCESS_COST + CALLVALUE + NEWACCOUNT - CALL_STIPEND
+        );
+
+        // Address zero is not special cased.
+        let other = address!("00000000000000000000000000000000000000dd");
+        assert_eq!(
+            call_empty_account_with_value(other),
+            (gas_used, U256::from(1))
+        );
+    }
+
+    #[test]
+    fn transient_storage() {
+        // Returns the transient value of slot zero and sets it to one.
+        let code = [
+            PUSH1, 0x00, TLOAD, PUSH1, 0x00, MSTORE, PUSH1, 0x01, PUSH1, 0x00, TSTORE, PUSH1, 0x20,
+            PUSH1, 0x00, RETURN,
+        ];
+        let mut evm = evm_with_code(&[(TARGET, &code)]);
+        evm.context.modify_tx(|tx| {
+            tx.transact_to = TxKind::Call(TARGET);
+            tx.gas_limit = 100_000;
+        });
+        // Transient storage is cleared at the end of each transaction.
+        for _ in 0..2 {
+            let result = evm.transact().unwrap().result;
+            assert!(result.is_success());
+            assert_eq!(result.output().unwrap()[..], [0u8; 32]);
+        }
+
+        evm.context.modify_cfg(|cfg| cfg.spec = SpecId::SHANGHAI);
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::NotActivated,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn transient_storage_reverted_in_sub_call() {
+        // Sets transient slot zero to one, delegate calls [CHILD] and returns the slot value.
+        let parent_code = [
+            PUSH1,
+            0x01,
+            PUSH1,
+            0x00,
+            TSTORE,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH2,
+            0x02,
+            0x00,
+            PUSH2,
+            0xff,
+            0xff,
+            DELEGATECALL,
+            POP,
+            PUSH1,
+            0x00,
+            TLOAD,
+            PUSH1,
+            0x00,
+            MSTORE,
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            RETURN,
+        ];
+        // Sets transient slot zero to two and reverts.
+        let child_code = [
+            PUSH1, 0x02, PUSH1, 0x00, TSTORE, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+        ];
+        let result = evm_with_code(&[(TARGET, &parent_code), (CHILD, &child_code)])
+            .simulate_call(TARGET, Bytes::new(), 100_000)
+            .unwrap();
+        assert!(result.is_success());
+        assert_eq!(U256::from_be_slice(result.output().unwrap()), U256::from(1));
+    }
+
+    #[test]
+    fn staticcall_state_change_fails_callee() {
+        // Returns `STATICCALL` success flag after writing its own storage.
+        let parent_code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH2, 0x02, 0x00, PUSH2, 0x27,
+            0x10, STATICCALL, PUSH1, 0x00, MSTORE, PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x20,
+            PUSH1, 0x00, RETURN,
+        ];
+        let child_code = [PUSH1, 0x01, PUSH1, 0x00, SSTORE];
+        let mut evm = evm_with_code(&[(TARGET, &parent_code), (CHILD, &child_code)]);
+        evm.context.modify_tx(|tx| {
+            tx.transact_to = TxKind::Call(TARGET);
+            tx.gas_limit = 100_000;
+        });
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.output().unwrap()[..], [0u8; 32]);
+        assert_eq!(
+            state[&TARGET].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+        assert!(state
+            .get(&CHILD)
+            .is_none_or(|account| account.storage.is_empty()));
+    }
+}
@@ -1 +1,95 @@
 pub use alloy_eip2930::{AccessList, AccessListItem};
+
+use primitives::{Address, HashMap};
+use std::vec::Vec;
+
+/// Extension methods for building an [AccessList].
+pub trait AccessListExt {
+    /// Merges `other` into this access list.
+    ///
+    /// Addresses and, per address, storage keys are unioned. Order of first appearance is
+    /// kept and the result has no duplicate addresses or keys.
+    fn merge(&mut self, other: AccessList);
+}
+
+impl AccessListExt for AccessList {
+    fn merge(&mut self, other: AccessList) {
+        let items = core::mem::take(&mut self.0);
+        let mut indices: HashMap<Address, usize> = HashMap::default();
+        let mut merged: Vec<AccessListItem> = Vec::with_capacity(items.len());
+        for item in items.into_iter().chain(other.0) {
+            let index = *indices.entry(item.address).or_insert_with(|| {
+                merged.push(AccessListItem {
+                    address: item.address,
+                    storage_keys: Vec::new(),
+                });
+                merged.len() - 1
+            });
+            let storage_keys = &mut merged[index].storage_keys;
+            for key in item.storage_keys {
+                if !storage_keys.contains(&key) {
+                    storage_keys.push(key);
+                }
+            }
+        }
+        self.0 = merged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, B256};
+    use std::vec;
+
+    #[test]
+    fn merge() {
+        let a = address!("00000000000000000000000000000000000000aa");
+        let b = address!("00000000000000000000000000000000000000bb");
+        let c = address!("00000000000000000000000000000000000000cc");
+        let key = B256::with_last_byte;
+
+        let mut list = AccessList(vec![
+            AccessListItem {
+                address: a,
+                storage_keys: vec![key(1), key(2)],
+            },
+            AccessListItem {
+                address: b,
+                storage_keys: vec![],
+            },
+        ]);
+        list.merge(AccessList(vec![
+            AccessListItem {
+                address: c,
+                storage_keys: vec![key(1)],
+            },
+            AccessListItem {
+                address: a,
+                storage_keys: vec![key(2), key(3)],
+            },
+            AccessListItem {
+                address: b,
+                storage_keys: vec![key(4), key(4)],
+            },
+        ]));
+
+        assert_eq!(
+            list,
+            AccessList(vec![
+                AccessListItem {
+                    address: a,
+                    storage_keys: vec![key(1), key(2), key(3)],
+                },
+                AccessListItem {
+                    address: b,
+                    storage_keys: vec![key(4)],
+                },
+                AccessListItem {
+                    address: c,
+                    storage_keys: vec![key(1)],
+                },
+            ])
+        );
+    }
+}